@@ -3,6 +3,10 @@ use std::env;
 fn main() {
     let target = env::var("TARGET").unwrap();
 
+    println!("cargo:rustc-check-cfg=cfg(riscv)");
+    println!("cargo:rustc-check-cfg=cfg(riscv32)");
+    println!("cargo:rustc-check-cfg=cfg(riscv64)");
+
     if target.starts_with("riscv32") {
         println!("cargo:rustc-cfg=riscv");
         println!("cargo:rustc-cfg=riscv32");
@@ -0,0 +1,23 @@
+#!/usr/bin/env bash
+# Checks that CSR accesses compile to a single CSR instruction in release builds.
+# Usage: ci/codegen.sh <riscv target>
+set -euo pipefail
+
+target=${1:-riscv32imac-unknown-none-elf}
+dir=$(dirname "$0")/codegen
+
+cargo rustc --quiet --release --target "$target" --manifest-path "$dir/Cargo.toml" -- --emit asm
+asm=$(ls "$dir"/target/"$target"/release/deps/riscv_codegen-*.s)
+
+status=0
+for function in $(grep -o '^codegen_[a-z_]*:' "$asm" | tr -d ':'); do
+    # Instructions of the function, skipping labels and assembler directives
+    body=$(sed -n "/^$function:/,/^\.Lfunc_end/p" "$asm" | grep -Ev '^\s*(\.|[A-Za-z_.$0-9]+:|#)' || true)
+    count=$(grep -c . <<< "$body" || true)
+    if [[ $count -ne 2 ]] || ! grep -q 'csr' <<< "$body" || ! grep -q 'ret' <<< "$body"; then
+        echo "error: $function does not compile to a single CSR instruction:"
+        echo "$body"
+        status=1
+    fi
+done
+exit $status
@@ -0,0 +1,51 @@
+//! Functions whose generated assembly is checked by `ci/codegen.sh`.
+//!
+//! Each function must compile to a single CSR instruction followed by `ret`.
+#![no_std]
+
+use riscv::register::{mcause, mcycle, mepc, mie, mstatus};
+
+#[no_mangle]
+pub fn codegen_mcause_read() -> usize {
+    mcause::read().bits()
+}
+
+#[no_mangle]
+pub fn codegen_mepc_read() -> usize {
+    mepc::read()
+}
+
+#[no_mangle]
+pub fn codegen_mstatus_read() -> usize {
+    mstatus::read().bits()
+}
+
+#[no_mangle]
+pub fn codegen_mcycle_read() -> usize {
+    mcycle::read()
+}
+
+#[no_mangle]
+pub fn codegen_mepc_write(bits: usize) {
+    mepc::write(bits)
+}
+
+#[no_mangle]
+pub unsafe fn codegen_mie_set_msoft() {
+    mie::set_msoft()
+}
+
+#[no_mangle]
+pub unsafe fn codegen_mie_clear_msoft() {
+    mie::clear_msoft()
+}
+
+#[no_mangle]
+pub unsafe fn codegen_mstatus_set_mie() {
+    mstatus::set_mie()
+}
+
+#[no_mangle]
+pub unsafe fn codegen_mstatus_clear_mie() {
+    mstatus::clear_mie()
+}
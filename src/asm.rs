@@ -122,3 +122,111 @@ pub unsafe fn delay(cycles: u32) {
         () => unimplemented!(),
     }
 }
+
+instruction!(
+    /// `NTL.P1` instruction wrapper (Zihintntl)
+    ///
+    /// Hints that the target of the next memory access does not exhibit temporal locality
+    /// within the capacity of the innermost level of private cache.
+    /// It is encoded as `add x0, x0, x2`, so it executes as a no-op on cores without Zihintntl.
+    , ntl_p1, "add x0, x0, x2");
+instruction!(
+    /// `NTL.PALL` instruction wrapper (Zihintntl)
+    ///
+    /// Hints that the target of the next memory access does not exhibit temporal locality
+    /// within the capacity of any level of private cache.
+    /// It is encoded as `add x0, x0, x3`, so it executes as a no-op on cores without Zihintntl.
+    , ntl_pall, "add x0, x0, x3");
+instruction!(
+    /// `NTL.S1` instruction wrapper (Zihintntl)
+    ///
+    /// Hints that the target of the next memory access does not exhibit temporal locality
+    /// within the capacity of the innermost level of shared cache.
+    /// It is encoded as `add x0, x0, x4`, so it executes as a no-op on cores without Zihintntl.
+    , ntl_s1, "add x0, x0, x4");
+instruction!(
+    /// `NTL.ALL` instruction wrapper (Zihintntl)
+    ///
+    /// Hints that the target of the next memory access does not exhibit temporal locality
+    /// within the capacity of any level of cache.
+    /// It is encoded as `add x0, x0, x5`, so it executes as a no-op on cores without Zihintntl.
+    , ntl_all, "add x0, x0, x5");
+
+/// Non-temporal locality hints of the Zihintntl extension
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Ntl {
+    /// Innermost level of private cache (see [`ntl_p1`])
+    P1,
+    /// All the levels of private cache (see [`ntl_pall`])
+    Pall,
+    /// Innermost level of shared cache (see [`ntl_s1`])
+    S1,
+    /// All the levels of cache (see [`ntl_all`])
+    All,
+}
+
+/// Copies a word with a non-temporal hint `$hint` before both the load and the store.
+#[cfg(riscv)]
+macro_rules! ntl_copy_word {
+    ($hint:literal, $src:expr, $dst:expr) => {
+        core::arch::asm!(
+            $hint,
+            concat!(xlen_access!("l"), " {tmp}, 0({src})"),
+            $hint,
+            concat!(xlen_access!("s"), " {tmp}, 0({dst})"),
+            src = in(reg) $src,
+            dst = in(reg) $dst,
+            tmp = out(reg) _,
+            options(nostack),
+        )
+    };
+}
+
+/// Returns the mnemonic of a word-sized load (`"l"`) or store (`"s"`).
+#[cfg(all(riscv, target_pointer_width = "32"))]
+macro_rules! xlen_access {
+    ($op:literal) => {
+        concat!($op, "w")
+    };
+}
+
+/// Returns the mnemonic of a word-sized load (`"l"`) or store (`"s"`).
+#[cfg(all(riscv, target_pointer_width = "64"))]
+macro_rules! xlen_access {
+    ($op:literal) => {
+        concat!($op, "d")
+    };
+}
+
+/// Copies `count` words from `src` to `dst` with a non-temporal locality hint before every access.
+///
+/// Zihintntl hints only apply to the memory access that immediately follows them.
+/// Thus, they cannot be added to an arbitrary copy routine (e.g., `core::ptr::copy_nonoverlapping`),
+/// and this function emits the hint before each load and store instead.
+/// Large buffer moves can use it to avoid evicting the working set from the cache.
+///
+/// # Safety
+///
+/// `src` must be valid for reads and `dst` must be valid for writes of `count` words.
+/// Both pointers must be aligned, and the regions must not overlap.
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn copy_nontemporal(dst: *mut usize, src: *const usize, count: usize, hint: Ntl) {
+    match () {
+        #[cfg(riscv)]
+        () => {
+            for i in 0..count {
+                let (src, dst) = (src.add(i), dst.add(i));
+                match hint {
+                    Ntl::P1 => ntl_copy_word!("add x0, x0, x2", src, dst),
+                    Ntl::Pall => ntl_copy_word!("add x0, x0, x3", src, dst),
+                    Ntl::S1 => ntl_copy_word!("add x0, x0, x4", src, dst),
+                    Ntl::All => ntl_copy_word!("add x0, x0, x5", src, dst),
+                }
+            }
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
@@ -0,0 +1,278 @@
+//! Atomic types for targets without the A extension
+//!
+//! These types offer the usual load, store and read-modify-write operations.
+//! Each operation is executed within a critical section via [`critical_section::with`],
+//! so they can be used to share data between thread mode and interrupt handlers.
+//!
+//! # Note
+//!
+//! The types are as sound as the `critical-section` implementation of the target.
+//! Single-hart targets can use the `critical-section-single-hart` feature, while
+//! multi-hart targets must use an implementation that also synchronizes the harts.
+//! Operations are sequentially consistent with respect to other critical sections.
+use core::cell::UnsafeCell;
+
+macro_rules! atomic_int {
+    ($(#[$attr:meta])* $name:ident, $int:ty) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        pub struct $name {
+            value: UnsafeCell<$int>,
+        }
+
+        // SAFETY: all the accesses are performed within a critical section
+        unsafe impl Sync for $name {}
+
+        impl $name {
+            /// Creates a new atomic value.
+            #[inline]
+            pub const fn new(value: $int) -> Self {
+                Self {
+                    value: UnsafeCell::new(value),
+                }
+            }
+
+            /// Returns a mutable reference to the underlying value.
+            #[inline]
+            pub fn get_mut(&mut self) -> &mut $int {
+                self.value.get_mut()
+            }
+
+            /// Consumes the atomic and returns the contained value.
+            #[inline]
+            pub fn into_inner(self) -> $int {
+                self.value.into_inner()
+            }
+
+            /// Loads the value.
+            #[inline]
+            pub fn load(&self) -> $int {
+                critical_section::with(|_| unsafe { *self.value.get() })
+            }
+
+            /// Stores a value.
+            #[inline]
+            pub fn store(&self, value: $int) {
+                critical_section::with(|_| unsafe { *self.value.get() = value })
+            }
+
+            /// Stores a value, returning the previous value.
+            #[inline]
+            pub fn swap(&self, value: $int) -> $int {
+                self.fetch_update(|_| value)
+            }
+
+            /// Stores `new` if the current value is `current`.
+            ///
+            /// It returns `Ok` with the previous value if the value was updated.
+            /// Otherwise, it returns `Err` with the current value.
+            #[inline]
+            pub fn compare_exchange(&self, current: $int, new: $int) -> Result<$int, $int> {
+                critical_section::with(|_| unsafe {
+                    let previous = *self.value.get();
+                    if previous == current {
+                        *self.value.get() = new;
+                        Ok(previous)
+                    } else {
+                        Err(previous)
+                    }
+                })
+            }
+
+            /// Adds to the current value (wrapping on overflow), returning the previous value.
+            #[inline]
+            pub fn fetch_add(&self, value: $int) -> $int {
+                self.fetch_update(|v| v.wrapping_add(value))
+            }
+
+            /// Subtracts from the current value (wrapping on overflow), returning the previous value.
+            #[inline]
+            pub fn fetch_sub(&self, value: $int) -> $int {
+                self.fetch_update(|v| v.wrapping_sub(value))
+            }
+
+            /// Bitwise "and" with the current value, returning the previous value.
+            #[inline]
+            pub fn fetch_and(&self, value: $int) -> $int {
+                self.fetch_update(|v| v & value)
+            }
+
+            /// Bitwise "or" with the current value, returning the previous value.
+            #[inline]
+            pub fn fetch_or(&self, value: $int) -> $int {
+                self.fetch_update(|v| v | value)
+            }
+
+            /// Bitwise "xor" with the current value, returning the previous value.
+            #[inline]
+            pub fn fetch_xor(&self, value: $int) -> $int {
+                self.fetch_update(|v| v ^ value)
+            }
+
+            /// Maximum with the current value, returning the previous value.
+            #[inline]
+            pub fn fetch_max(&self, value: $int) -> $int {
+                self.fetch_update(|v| v.max(value))
+            }
+
+            /// Minimum with the current value, returning the previous value.
+            #[inline]
+            pub fn fetch_min(&self, value: $int) -> $int {
+                self.fetch_update(|v| v.min(value))
+            }
+
+            /// Applies `f` to the current value and stores the result, returning the previous value.
+            ///
+            /// `f` is executed within a critical section, so it should be short.
+            #[inline]
+            pub fn fetch_update<F: FnOnce($int) -> $int>(&self, f: F) -> $int {
+                critical_section::with(|_| unsafe {
+                    let previous = *self.value.get();
+                    *self.value.get() = f(previous);
+                    previous
+                })
+            }
+        }
+
+        impl Default for $name {
+            #[inline]
+            fn default() -> Self {
+                Self::new(0)
+            }
+        }
+    };
+}
+
+atomic_int!(
+    /// An 8-bit unsigned integer that can be shared with interrupt handlers
+    AtomicU8, u8);
+atomic_int!(
+    /// A 16-bit unsigned integer that can be shared with interrupt handlers
+    AtomicU16, u16);
+atomic_int!(
+    /// A 32-bit unsigned integer that can be shared with interrupt handlers
+    AtomicU32, u32);
+atomic_int!(
+    /// A 64-bit unsigned integer that can be shared with interrupt handlers
+    AtomicU64, u64);
+atomic_int!(
+    /// A pointer-sized unsigned integer that can be shared with interrupt handlers
+    AtomicUsize, usize);
+atomic_int!(
+    /// An 8-bit signed integer that can be shared with interrupt handlers
+    AtomicI8, i8);
+atomic_int!(
+    /// A 16-bit signed integer that can be shared with interrupt handlers
+    AtomicI16, i16);
+atomic_int!(
+    /// A 32-bit signed integer that can be shared with interrupt handlers
+    AtomicI32, i32);
+atomic_int!(
+    /// A 64-bit signed integer that can be shared with interrupt handlers
+    AtomicI64, i64);
+atomic_int!(
+    /// A pointer-sized signed integer that can be shared with interrupt handlers
+    AtomicIsize, isize);
+
+/// A boolean that can be shared with interrupt handlers
+#[repr(transparent)]
+pub struct AtomicBool {
+    value: UnsafeCell<bool>,
+}
+
+// SAFETY: all the accesses are performed within a critical section
+unsafe impl Sync for AtomicBool {}
+
+impl AtomicBool {
+    /// Creates a new atomic boolean.
+    #[inline]
+    pub const fn new(value: bool) -> Self {
+        Self {
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a mutable reference to the underlying boolean.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut bool {
+        self.value.get_mut()
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    #[inline]
+    pub fn into_inner(self) -> bool {
+        self.value.into_inner()
+    }
+
+    /// Loads the value.
+    #[inline]
+    pub fn load(&self) -> bool {
+        critical_section::with(|_| unsafe { *self.value.get() })
+    }
+
+    /// Stores a value.
+    #[inline]
+    pub fn store(&self, value: bool) {
+        critical_section::with(|_| unsafe { *self.value.get() = value })
+    }
+
+    /// Stores a value, returning the previous value.
+    #[inline]
+    pub fn swap(&self, value: bool) -> bool {
+        self.fetch_update(|_| value)
+    }
+
+    /// Stores `new` if the current value is `current`.
+    ///
+    /// It returns `Ok` with the previous value if the value was updated.
+    /// Otherwise, it returns `Err` with the current value.
+    #[inline]
+    pub fn compare_exchange(&self, current: bool, new: bool) -> Result<bool, bool> {
+        critical_section::with(|_| unsafe {
+            let previous = *self.value.get();
+            if previous == current {
+                *self.value.get() = new;
+                Ok(previous)
+            } else {
+                Err(previous)
+            }
+        })
+    }
+
+    /// Logical "and" with the current value, returning the previous value.
+    #[inline]
+    pub fn fetch_and(&self, value: bool) -> bool {
+        self.fetch_update(|v| v & value)
+    }
+
+    /// Logical "or" with the current value, returning the previous value.
+    #[inline]
+    pub fn fetch_or(&self, value: bool) -> bool {
+        self.fetch_update(|v| v | value)
+    }
+
+    /// Logical "xor" with the current value, returning the previous value.
+    #[inline]
+    pub fn fetch_xor(&self, value: bool) -> bool {
+        self.fetch_update(|v| v ^ value)
+    }
+
+    /// Applies `f` to the current value and stores the result, returning the previous value.
+    ///
+    /// `f` is executed within a critical section, so it should be short.
+    #[inline]
+    pub fn fetch_update<F: FnOnce(bool) -> bool>(&self, f: F) -> bool {
+        critical_section::with(|_| unsafe {
+            let previous = *self.value.get();
+            *self.value.get() = f(previous);
+            previous
+        })
+    }
+}
+
+impl Default for AtomicBool {
+    #[inline]
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
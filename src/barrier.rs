@@ -0,0 +1,116 @@
+//! Hart synchronization primitives
+//!
+//! [`HartBarrier`] lets a fixed number of harts rendezvous (e.g., "all harts finished cache init")
+//! before continuing. [`elect_boot_hart`] picks exactly one hart to run the initialization code.
+//! They are based on atomic memory operations, so they require the A extension.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::barrier::HartBarrier;
+//!
+//! static INIT: HartBarrier = HartBarrier::new(4);
+//!
+//! fn hart_main() {
+//!     // per-hart initialization here
+//!     if INIT.wait() {
+//!         // only the last hart to arrive gets here
+//!     }
+//!     // all the harts are initialized
+//! }
+//! ```
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// Barrier for a fixed number of harts.
+///
+/// The barrier is reusable: once all the harts are released, it is ready for the next rendezvous.
+pub struct HartBarrier {
+    harts: usize,
+    count: AtomicUsize,
+    generation: AtomicUsize,
+}
+
+impl HartBarrier {
+    /// Creates a new barrier for `harts` harts.
+    #[inline]
+    pub const fn new(harts: usize) -> Self {
+        assert!(harts > 0, "a barrier requires at least one hart");
+        Self {
+            harts,
+            count: AtomicUsize::new(0),
+            generation: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the number of harts of the barrier.
+    #[inline]
+    pub const fn harts(&self) -> usize {
+        self.harts
+    }
+
+    /// Blocks the current hart until all the harts have called this method, spinning while waiting.
+    ///
+    /// It returns `true` for the last hart to arrive, and `false` for the rest.
+    #[inline]
+    pub fn wait(&self) -> bool {
+        self.wait_with(|| {}, core::hint::spin_loop)
+    }
+
+    /// Blocks the current hart until all the harts have called this method.
+    ///
+    /// The last hart to arrive calls `wake` (e.g., to send an IPI to all the harts with
+    /// `CLINT::set_msip`). The other harts call `idle` until they are
+    /// released (e.g., to execute `WFI`). As `idle` may return for other reasons (e.g., spurious
+    /// wake-ups), the barrier is checked again after each call.
+    ///
+    /// It returns `true` for the last hart to arrive, and `false` for the rest.
+    ///
+    /// # Note
+    ///
+    /// If `idle` executes `WFI`, the IPI must remain pending (or be serviced) for `WFI` to complete.
+    /// Otherwise, a hart that checks the barrier right before the IPI is sent may never wake up.
+    #[inline]
+    pub fn wait_with<W: FnOnce(), I: FnMut()>(&self, wake: W, mut idle: I) -> bool {
+        let generation = self.generation.load(Ordering::Acquire);
+        if self.count.fetch_add(1, Ordering::AcqRel) + 1 == self.harts {
+            self.count.store(0, Ordering::Relaxed);
+            self.generation.fetch_add(1, Ordering::Release);
+            wake();
+            true
+        } else {
+            while self.generation.load(Ordering::Acquire) == generation {
+                idle();
+            }
+            false
+        }
+    }
+}
+
+/// Elects the boot hart. It returns `true` for exactly one of the harts that call it with `flag`.
+///
+/// The election is an atomic swap (i.e., `amoswap`) of `flag`, which must be 0 before any hart
+/// calls this function (e.g., a `static` in `.data`, which is not cleared by the other harts).
+///
+/// # Example
+///
+/// ``` no_run
+/// use core::sync::atomic::AtomicUsize;
+/// use riscv::barrier::elect_boot_hart;
+///
+/// static BOOT: AtomicUsize = AtomicUsize::new(0);
+///
+/// fn reset_handler() {
+///     if elect_boot_hart(&BOOT) {
+///         // initialize the system here
+///     } else {
+///         loop {
+///             // park the hart until the boot hart releases it
+///             unsafe { riscv::asm::wfi() };
+///         }
+///     }
+/// }
+/// ```
+#[inline]
+pub fn elect_boot_hart(flag: &AtomicUsize) -> bool {
+    flag.swap(1, Ordering::AcqRel) == 0
+}
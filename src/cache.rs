@@ -0,0 +1,309 @@
+//! Cache maintenance operations
+//!
+//! [`CacheOps`] abstracts the data cache maintenance operations that DMA drivers need
+//! (clean, invalidate and flush by address range), plus instruction cache synchronization.
+//! Thus, HAL code can be written once against the trait and work with any of the implementations:
+//!
+//! - [`Zicbom`]: standard cache-block management instructions (`cbo.*`), optionally with
+//!   the cache-block zero instruction of Zicboz.
+//! - [`TheadCache`]: T-Head vendor instructions (`dcache.*va` and `sync.s`), e.g., in the C906 and C910.
+//! - [`SifiveL1`]: SiFive L1 data cache control instructions (`cflush.d.l1` and `cdiscard.d.l1`).
+//!   They are only available in M-mode.
+//!
+//! Instructions are emitted with their raw encodings, so no target feature is required to build them.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::cache::{CacheOps, Zicbom};
+//!
+//! fn start_dma_rx<C: CacheOps>(cache: &C, buffer: &mut [u8]) {
+//!     // discard stale cache lines before the DMA writes to memory
+//!     unsafe { cache.invalidate(buffer.as_ptr() as usize, buffer.len()) };
+//!     // start the DMA transfer here
+//! }
+//!
+//! let cache = Zicbom::new(64); // cache block size from the device tree
+//! start_dma_rx(&cache, &mut [0; 256]);
+//! ```
+
+/// Cache maintenance operations by address range.
+///
+/// Operations apply to all the cache blocks that overlap with `addr..addr + len`.
+pub trait CacheOps {
+    /// Returns the size (in bytes) of the cache blocks.
+    fn block_size(&self) -> usize;
+
+    /// Writes back the dirty cache blocks of a range to memory. The blocks remain valid.
+    ///
+    /// # Safety
+    ///
+    /// The range must be mapped in memory.
+    unsafe fn clean(&self, addr: usize, len: usize);
+
+    /// Invalidates the cache blocks of a range without writing them back to memory.
+    ///
+    /// # Safety
+    ///
+    /// The range must be mapped in memory. Pending writes to the range are lost, even for data
+    /// of the blocks that overlap with the range but lie outside of it.
+    unsafe fn invalidate(&self, addr: usize, len: usize);
+
+    /// Writes back the dirty cache blocks of a range to memory and invalidates them.
+    ///
+    /// # Safety
+    ///
+    /// The range must be mapped in memory.
+    unsafe fn flush(&self, addr: usize, len: usize);
+
+    /// Sets a range of memory to zero. The default implementation uses volatile writes.
+    ///
+    /// # Safety
+    ///
+    /// The range must be valid for writes.
+    unsafe fn zero(&self, addr: usize, len: usize) {
+        for i in 0..len {
+            ((addr + i) as *mut u8).write_volatile(0);
+        }
+    }
+
+    /// Synchronizes the instruction cache with previous writes to memory (i.e., `fence.i`).
+    #[inline]
+    fn sync_icache(&self) {
+        match () {
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            () => unsafe { core::arch::asm!("fence.i", options(nostack)) },
+            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// Calls `f` with the base address of every block of size `block` that overlaps with `addr..addr + len`.
+#[inline]
+fn for_each_block<F: FnMut(usize)>(addr: usize, len: usize, block: usize, mut f: F) {
+    if len == 0 {
+        return;
+    }
+    let mut base = addr & !(block - 1);
+    let end = addr.saturating_add(len);
+    while base < end {
+        f(base);
+        base = match base.checked_add(block) {
+            Some(next) => next,
+            None => break,
+        };
+    }
+}
+
+/// Emits a raw instruction that takes its address operand in `a0`.
+macro_rules! insn_a0 {
+    ($encoding:literal, $addr:expr) => {
+        match () {
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            () => core::arch::asm!(concat!(".word ", $encoding), in("a0") $addr, options(nostack)),
+            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+            () => {
+                let _ = $addr;
+                unimplemented!()
+            }
+        }
+    };
+}
+
+/// Standard cache-block management (Zicbom) and cache-block zero (Zicboz) instructions.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Zicbom {
+    cbom_block_size: usize,
+    cboz_block_size: Option<usize>,
+}
+
+impl Zicbom {
+    /// Creates a new interface for Zicbom with the given cache block size (`riscv,cbom-block-size`).
+    #[inline]
+    pub const fn new(cbom_block_size: usize) -> Self {
+        assert!(
+            cbom_block_size.is_power_of_two(),
+            "cache block size must be a power of two"
+        );
+        Self {
+            cbom_block_size,
+            cboz_block_size: None,
+        }
+    }
+
+    /// Enables Zicboz with the given cache block size (`riscv,cboz-block-size`).
+    /// Then, [`CacheOps::zero`] uses `cbo.zero` for whole blocks.
+    #[inline]
+    pub const fn with_zicboz(self, cboz_block_size: usize) -> Self {
+        assert!(
+            cboz_block_size.is_power_of_two(),
+            "cache block size must be a power of two"
+        );
+        Self {
+            cbom_block_size: self.cbom_block_size,
+            cboz_block_size: Some(cboz_block_size),
+        }
+    }
+}
+
+impl CacheOps for Zicbom {
+    #[inline]
+    fn block_size(&self) -> usize {
+        self.cbom_block_size
+    }
+
+    #[inline]
+    unsafe fn clean(&self, addr: usize, len: usize) {
+        // cbo.clean (a0)
+        for_each_block(addr, len, self.cbom_block_size, |block| {
+            insn_a0!("0x0015200F", block)
+        });
+    }
+
+    #[inline]
+    unsafe fn invalidate(&self, addr: usize, len: usize) {
+        // cbo.inval (a0)
+        for_each_block(addr, len, self.cbom_block_size, |block| {
+            insn_a0!("0x0005200F", block)
+        });
+    }
+
+    #[inline]
+    unsafe fn flush(&self, addr: usize, len: usize) {
+        // cbo.flush (a0)
+        for_each_block(addr, len, self.cbom_block_size, |block| {
+            insn_a0!("0x0025200F", block)
+        });
+    }
+
+    #[inline]
+    unsafe fn zero(&self, addr: usize, len: usize) {
+        let block = match self.cboz_block_size {
+            Some(block) => block,
+            None => {
+                for i in 0..len {
+                    ((addr + i) as *mut u8).write_volatile(0);
+                }
+                return;
+            }
+        };
+        let end = addr + len;
+        let first = (addr + block - 1) & !(block - 1);
+        let last = end & !(block - 1);
+        if first >= last {
+            for i in addr..end {
+                (i as *mut u8).write_volatile(0);
+            }
+            return;
+        }
+        for i in addr..first {
+            (i as *mut u8).write_volatile(0);
+        }
+        // cbo.zero (a0)
+        for_each_block(first, last - first, block, |block| {
+            insn_a0!("0x0045200F", block)
+        });
+        for i in last..end {
+            (i as *mut u8).write_volatile(0);
+        }
+    }
+}
+
+/// T-Head vendor cache instructions (XTheadCmo), e.g., in the C906 and C910.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct TheadCache;
+
+impl TheadCache {
+    /// Size (in bytes) of the cache blocks.
+    pub const BLOCK_SIZE: usize = 64;
+
+    /// Waits for the completion of previous cache operations (i.e., `sync.s`).
+    #[inline]
+    fn sync() {
+        match () {
+            #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+            () => unsafe { core::arch::asm!(".word 0x0190000B", options(nostack)) },
+            #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+            () => unimplemented!(),
+        }
+    }
+}
+
+impl CacheOps for TheadCache {
+    #[inline]
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+
+    #[inline]
+    unsafe fn clean(&self, addr: usize, len: usize) {
+        // dcache.cva a0
+        for_each_block(addr, len, Self::BLOCK_SIZE, |block| {
+            insn_a0!("0x0255000B", block)
+        });
+        Self::sync();
+    }
+
+    #[inline]
+    unsafe fn invalidate(&self, addr: usize, len: usize) {
+        // dcache.iva a0
+        for_each_block(addr, len, Self::BLOCK_SIZE, |block| {
+            insn_a0!("0x0265000B", block)
+        });
+        Self::sync();
+    }
+
+    #[inline]
+    unsafe fn flush(&self, addr: usize, len: usize) {
+        // dcache.civa a0
+        for_each_block(addr, len, Self::BLOCK_SIZE, |block| {
+            insn_a0!("0x0275000B", block)
+        });
+        Self::sync();
+    }
+}
+
+/// SiFive L1 data cache control instructions, e.g., in the U54 and U74 cores.
+///
+/// # Note
+///
+/// These instructions are only available in M-mode. There is no clean-only instruction,
+/// so [`CacheOps::clean`] also invalidates the cache blocks.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SifiveL1;
+
+impl SifiveL1 {
+    /// Size (in bytes) of the cache blocks.
+    pub const BLOCK_SIZE: usize = 64;
+}
+
+impl CacheOps for SifiveL1 {
+    #[inline]
+    fn block_size(&self) -> usize {
+        Self::BLOCK_SIZE
+    }
+
+    #[inline]
+    unsafe fn clean(&self, addr: usize, len: usize) {
+        self.flush(addr, len);
+    }
+
+    #[inline]
+    unsafe fn invalidate(&self, addr: usize, len: usize) {
+        // cdiscard.d.l1 a0
+        for_each_block(addr, len, Self::BLOCK_SIZE, |block| {
+            insn_a0!("0xFC250073", block)
+        });
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+
+    #[inline]
+    unsafe fn flush(&self, addr: usize, len: usize) {
+        // cflush.d.l1 a0
+        for_each_block(addr, len, Self::BLOCK_SIZE, |block| {
+            insn_a0!("0xFC050073", block)
+        });
+        core::sync::atomic::fence(core::sync::atomic::Ordering::SeqCst);
+    }
+}
@@ -0,0 +1,171 @@
+//! Hardware breakpoints and watchpoints
+//!
+//! High-level helpers on top of the trigger module of the RISC-V Debug Specification.
+//! They look for a free address/data match trigger (`mcontrol` or `mcontrol6`)
+//! and program it to raise a breakpoint exception when it matches.
+//!
+//! # Note
+//!
+//! Triggers are discovered through the `tinfo` register, which must be implemented.
+//! The programmed triggers match in M-mode, S-mode and U-mode. When a trigger matches in M-mode,
+//! the breakpoint exception is also taken in M-mode. Trap handlers must not hit the trigger again
+//! (e.g., by clearing `tcontrol.mte` on cores that implement it).
+use crate::register::{tdata1, tdata1::Type, tdata2, tinfo, tselect};
+
+/// Trigger matches loads
+const LOAD: usize = 1 << 0;
+/// Trigger matches stores
+const STORE: usize = 1 << 1;
+/// Trigger matches instruction fetches
+const EXECUTE: usize = 1 << 2;
+/// Trigger matches in U-mode
+const U: usize = 1 << 3;
+/// Trigger matches in S-mode
+const S: usize = 1 << 4;
+/// Trigger matches in M-mode
+const M: usize = 1 << 6;
+/// Trigger matches when the address is equal to `tdata2`
+const MATCH_EQUAL: usize = 0 << 7;
+/// Trigger matches when the address is in the NAPOT range encoded in `tdata2`
+const MATCH_NAPOT: usize = 1 << 7;
+/// Fields that must read back as written for a trigger to be usable
+const CONFIG_MASK: usize = 0xFFC7;
+
+/// Memory accesses that fire a watchpoint
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Access {
+    Load,
+    Store,
+    LoadStore,
+}
+
+impl Access {
+    #[inline]
+    fn bits(self) -> usize {
+        match self {
+            Access::Load => LOAD,
+            Access::Store => STORE,
+            Access::LoadStore => LOAD | STORE,
+        }
+    }
+}
+
+/// Errors returned when programming a trigger
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// There is no free trigger supporting the requested configuration
+    NoFreeTrigger,
+    /// The watched range is not a naturally aligned power of two
+    InvalidRange,
+}
+
+/// A programmed trigger
+#[derive(Debug)]
+pub struct Trigger {
+    index: usize,
+}
+
+impl Trigger {
+    /// Returns the index of the trigger (i.e., its `tselect` value)
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Disables the trigger, making it available again.
+    ///
+    /// # Safety
+    ///
+    /// It modifies `tselect`, which may be in use by an interrupted context.
+    #[inline]
+    pub unsafe fn clear(self) {
+        tselect::write(self.index);
+        tdata1::write(0);
+    }
+}
+
+/// Sets a breakpoint on the instruction at `addr`.
+///
+/// The breakpoint fires before the instruction is executed.
+///
+/// # Safety
+///
+/// It modifies `tselect`, which may be in use by an interrupted context.
+#[inline]
+pub unsafe fn set_breakpoint(addr: usize) -> Result<Trigger, Error> {
+    program(addr, EXECUTE | M | S | U | MATCH_EQUAL)
+}
+
+/// Sets a watchpoint on the `size` bytes starting at `addr`.
+///
+/// `size` must be a power of two and `addr` must be aligned to `size`.
+///
+/// # Safety
+///
+/// It modifies `tselect`, which may be in use by an interrupted context.
+#[inline]
+pub unsafe fn set_watchpoint(addr: usize, size: usize, access: Access) -> Result<Trigger, Error> {
+    if !size.is_power_of_two() || addr & (size - 1) != 0 {
+        return Err(Error::InvalidRange);
+    }
+    match size {
+        1 => program(addr, access.bits() | M | S | U | MATCH_EQUAL),
+        _ => program(
+            addr | (size / 2 - 1),
+            access.bits() | M | S | U | MATCH_NAPOT,
+        ),
+    }
+}
+
+/// Programs the first free match trigger that accepts the configuration.
+unsafe fn program(address: usize, config: usize) -> Result<Trigger, Error> {
+    let type_offset = usize::BITS as usize - 4;
+    let mut index = 0;
+    loop {
+        tselect::write(index);
+        let info = tinfo::read();
+        // Selecting a trigger that does not exist stops the search
+        if tselect::read() != index || info.info() == 1 {
+            return Err(Error::NoFreeTrigger);
+        }
+        let trigger_type = if info.supports(Type::Mcontrol6) {
+            Some(Type::Mcontrol6)
+        } else if info.supports(Type::Mcontrol) {
+            Some(Type::Mcontrol)
+        } else {
+            None
+        };
+        if let Some(trigger_type) = trigger_type.filter(|_| is_free(tdata1::read())) {
+            let bits = ((trigger_type as usize) << type_offset) | config;
+            // Disable the trigger while tdata2 is updated
+            tdata1::write(0);
+            tdata2::write(address);
+            tdata1::write(bits);
+            let readback = tdata1::read().bits();
+            if readback >> type_offset == trigger_type as usize
+                && readback & CONFIG_MASK == bits & CONFIG_MASK
+                && tdata2::read() == address
+            {
+                return Ok(Trigger { index });
+            }
+            // The trigger does not support this configuration
+            tdata1::write(0);
+        }
+        index += 1;
+    }
+}
+
+/// Checks if a trigger is not owned by Debug Mode and is not in use.
+#[inline]
+fn is_free(tdata1: tdata1::Tdata1) -> bool {
+    if tdata1.dmode() {
+        return false;
+    }
+    match tdata1.trigger_type() {
+        Some(Type::None) | Some(Type::Disabled) => true,
+        Some(Type::Mcontrol) | Some(Type::Mcontrol6) => {
+            tdata1.data() & (EXECUTE | STORE | LOAD) == 0
+        }
+        _ => false,
+    }
+}
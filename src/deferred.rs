@@ -0,0 +1,177 @@
+//! Deferred work for interrupt bottom halves
+//!
+//! [`Queue`] is a fixed-capacity FIFO queue. Interrupt handlers push work items
+//! (the "top half" only does the time-critical part), and a lower priority context drains
+//! them later (the "bottom half"). Draining is usually triggered by pending a software
+//! interrupt with [`Queue::push_and_pend`], e.g., `mip::set_ssoft` or `CLINT::set_msip`.
+//!
+//! # Note
+//!
+//! Each operation is executed within a critical section via [`critical_section::with`].
+//! Multi-hart targets must use a `critical-section` implementation that also synchronizes the harts.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::{deferred::Queue, register::mip};
+//! # fn process(_byte: u8) {}
+//!
+//! static WORK: Queue<u8, 16> = Queue::new();
+//!
+//! // Top half: UART receive interrupt
+//! fn uart_rx(byte: u8) {
+//!     // SAFETY: the supervisor software interrupt only drains the queue
+//!     let _ = WORK.push_and_pend(byte, || unsafe { mip::set_ssoft() });
+//! }
+//!
+//! // Bottom half: supervisor software interrupt
+//! fn supervisor_soft() {
+//!     unsafe { mip::clear_ssoft() };
+//!     WORK.drain(|byte| process(byte));
+//! }
+//! ```
+
+use core::cell::RefCell;
+use core::mem::MaybeUninit;
+use critical_section::Mutex;
+
+/// Fixed-capacity FIFO queue of deferred work items.
+pub struct Queue<T, const N: usize> {
+    inner: Mutex<RefCell<Inner<T, N>>>,
+}
+
+/// State of a [`Queue`].
+struct Inner<T, const N: usize> {
+    /// Index of the oldest item.
+    head: usize,
+    /// Number of items in the queue.
+    len: usize,
+    /// Storage of the items.
+    buffer: MaybeUninit<[T; N]>,
+}
+
+impl<T, const N: usize> Queue<T, N> {
+    /// Creates a new, empty queue.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(Inner {
+                head: 0,
+                len: 0,
+                buffer: MaybeUninit::uninit(),
+            })),
+        }
+    }
+
+    /// Returns the maximum number of items of the queue.
+    #[inline]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of items in the queue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.inner.borrow(cs).borrow().len)
+    }
+
+    /// Checks if the queue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if the queue is full.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == N
+    }
+
+    /// Appends an item to the back of the queue.
+    /// If the queue is full, it returns an error with the item back.
+    #[inline]
+    pub fn push(&self, value: T) -> Result<(), T> {
+        critical_section::with(|cs| {
+            let mut inner = self.inner.borrow(cs).borrow_mut();
+            if inner.len == N {
+                return Err(value);
+            }
+            let index = (inner.head + inner.len) % N;
+            // SAFETY: the slot is free and within the buffer
+            unsafe { inner.slot(index).write(value) };
+            inner.len += 1;
+            Ok(())
+        })
+    }
+
+    /// Appends an item to the back of the queue and calls `pend` to trigger the bottom half.
+    /// If the queue is full, `pend` is not called and it returns an error with the item back.
+    ///
+    /// `pend` is called even if the queue was not empty, as the bottom half may be already running.
+    #[inline]
+    pub fn push_and_pend<F: FnOnce()>(&self, value: T, pend: F) -> Result<(), T> {
+        let result = self.push(value);
+        if result.is_ok() {
+            pend();
+        }
+        result
+    }
+
+    /// Removes the item at the front of the queue. It returns `None` if the queue is empty.
+    #[inline]
+    pub fn pop(&self) -> Option<T> {
+        critical_section::with(|cs| {
+            let mut inner = self.inner.borrow(cs).borrow_mut();
+            if inner.len == 0 {
+                return None;
+            }
+            let head = inner.head;
+            // SAFETY: the slot is occupied and within the buffer
+            let value = unsafe { inner.slot(head).read() };
+            inner.head = (head + 1) % N;
+            inner.len -= 1;
+            Some(value)
+        })
+    }
+
+    /// Removes all the items of the queue and calls `f` on each of them.
+    ///
+    /// The critical section only covers the removal of each item, not the execution of `f`.
+    /// Thus, items pushed while draining are also processed. It returns the number of processed items.
+    #[inline]
+    pub fn drain<F: FnMut(T)>(&self, mut f: F) -> usize {
+        let mut count = 0;
+        while let Some(value) = self.pop() {
+            f(value);
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<T, const N: usize> Inner<T, N> {
+    /// Returns a pointer to a slot of the buffer.
+    #[inline(always)]
+    fn slot(&mut self, index: usize) -> *mut T {
+        debug_assert!(index < N);
+        // SAFETY: index is within the buffer
+        unsafe { (self.buffer.as_mut_ptr() as *mut T).add(index) }
+    }
+}
+
+impl<T, const N: usize> Default for Queue<T, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> Drop for Inner<T, N> {
+    fn drop(&mut self) {
+        for i in 0..self.len {
+            let index = (self.head + i) % N;
+            // SAFETY: the slot is occupied and within the buffer
+            unsafe { self.slot(index).drop_in_place() };
+        }
+    }
+}
@@ -1,5 +1,5 @@
 //! Delay devices and providers
-use crate::register::mcycle;
+use crate::register::{mcycle, time};
 use embedded_hal::blocking::delay::{DelayMs, DelayUs};
 
 /// Machine mode cycle counter (`mcycle`) as a delay provider
@@ -85,3 +85,142 @@ impl DelayMs<u8> for McycleDelay {
         self.delay_ms(ms as u32)
     }
 }
+
+/// Busy-wait delay provider calibrated against a reference counter.
+///
+/// It uses [`crate::asm::delay`] loops, so it does not require any timer interrupt.
+/// Calibration measures how long a fixed number of loops take against a reference counter
+/// with a known frequency (e.g., `mtime` or `mcycle`). The overhead of reading the counter and
+/// entering the loop is measured separately and discarded, and one tick is subtracted to account
+/// for the quantization of the counter. Thus, the calibration never overestimates the duration
+/// of a loop, and delays take *at least* the requested time unless the core frequency changes
+/// after calibration (or interrupts are serviced during calibration).
+///
+/// Delays are longer than requested by at most the relative calibration error, which is bounded
+/// by `3 / ticks` (i.e., up to three ticks of quantization and overhead error over the `ticks`
+/// measured during calibration), plus the overhead of one call. Slow reference counters
+/// (e.g., `mtime`) yield a coarser calibration than `mcycle`.
+#[derive(Copy, Clone)]
+pub struct CalibratedDelay {
+    /// Loop units per microsecond, in 16.16 fixed point
+    units_us: u64,
+}
+
+impl CalibratedDelay {
+    /// Number of loop units executed during calibration
+    const CALIBRATION_UNITS: u32 = 100_000;
+
+    /// Calibrates the delay provider against a reference counter.
+    /// `read` must return the current value of the counter, which runs at `ticks_second` Hertz.
+    #[inline]
+    pub fn calibrate(read: fn() -> u64, ticks_second: u32) -> Self {
+        let baseline = Self::measure(read, 0);
+        let ticks = Self::measure(read, Self::CALIBRATION_UNITS);
+        // Discard the overhead and one tick of quantization, so the loop duration is never overestimated
+        let ticks = ticks.saturating_sub(baseline).saturating_sub(1).max(1);
+        let numerator = (Self::CALIBRATION_UNITS as u128 * ticks_second as u128) << 16;
+        let denominator = ticks as u128 * 1_000_000;
+        // Round up so delays are never shorter than requested
+        let units_us = (numerator + denominator - 1) / denominator;
+        Self {
+            units_us: (units_us as u64).max(1),
+        }
+    }
+
+    /// Returns the ticks of the reference counter elapsed while running `units` loop units.
+    #[inline]
+    fn measure(read: fn() -> u64, units: u32) -> u64 {
+        let t0 = read();
+        // SAFETY: busy-wait loop with no side effects
+        unsafe { crate::asm::delay(units) };
+        read().wrapping_sub(t0)
+    }
+
+    /// Calibrates the delay provider against `mtime`, read through the `time` CSR.
+    /// `ticks_second` must be the timebase frequency of the platform, in Hertz.
+    #[inline]
+    pub fn calibrate_mtime(ticks_second: u32) -> Self {
+        Self::calibrate(time::read64, ticks_second)
+    }
+
+    /// Calibrates the delay provider against `mcycle`.
+    /// `ticks_second` must be the clock speed of the core, in Hertz.
+    #[inline]
+    pub fn calibrate_mcycle(ticks_second: u32) -> Self {
+        Self::calibrate(mcycle::read64, ticks_second)
+    }
+}
+
+impl DelayUs<u64> for CalibratedDelay {
+    #[inline]
+    fn delay_us(&mut self, us: u64) {
+        // Round up to never delay less than requested
+        let mut units = (us as u128 * self.units_us as u128 + 0xFFFF) >> 16;
+        while units > 0 {
+            let chunk = units.min(u32::MAX as u128);
+            // SAFETY: busy-wait loop with no side effects
+            unsafe { crate::asm::delay(chunk as u32) };
+            units -= chunk;
+        }
+    }
+}
+
+impl DelayUs<u32> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_us(&mut self, us: u32) {
+        self.delay_us(us as u64)
+    }
+}
+
+// Implemented for constructions like `delay.delay_us(50_000);`
+impl DelayUs<i32> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_us(&mut self, us: i32) {
+        assert!(us >= 0);
+        self.delay_us(us as u32);
+    }
+}
+
+impl DelayUs<u16> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_us(&mut self, us: u16) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl DelayUs<u8> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_us(&mut self, us: u8) {
+        self.delay_us(us as u32)
+    }
+}
+
+impl DelayMs<u32> for CalibratedDelay {
+    #[inline]
+    fn delay_ms(&mut self, ms: u32) {
+        self.delay_us((ms as u64) * 1000)
+    }
+}
+
+// Implemented for constructions like `delay.delay_ms(50_000);`
+impl DelayMs<i32> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_ms(&mut self, ms: i32) {
+        assert!(ms >= 0);
+        self.delay_ms(ms as u32);
+    }
+}
+
+impl DelayMs<u16> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_ms(&mut self, ms: u16) {
+        self.delay_ms(ms as u32)
+    }
+}
+
+impl DelayMs<u8> for CalibratedDelay {
+    #[inline(always)]
+    fn delay_ms(&mut self, ms: u8) {
+        self.delay_ms(ms as u32)
+    }
+}
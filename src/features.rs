@@ -0,0 +1,146 @@
+//! Runtime CPU feature detection
+//!
+//! [`detect`] combines the `misa` register, trap-and-recover probing of instructions
+//! from extensions that `misa` does not report, and vendor-specific knowledge into a [`CpuFeatures`]
+//! structure. Portable firmware can use it at boot to select code paths.
+//!
+//! # Note
+//!
+//! Probing temporarily installs a trap handler in `mtvec` (direct mode).
+//! It must be executed in M-mode, and the core must accept a 4-byte aligned `mtvec` in direct mode.
+use crate::register::mvendorid;
+#[cfg(riscv)]
+use crate::{interrupt, register::misa};
+
+/// JEDEC manufacturer ID ([`mvendorid`]) of SiFive.
+const SIFIVE: usize = 0x489;
+/// JEDEC manufacturer ID ([`mvendorid`]) of T-Head.
+const THEAD: usize = 0x5b7;
+/// JEDEC manufacturer ID ([`mvendorid`]) of Andes.
+const ANDES: usize = 0x31e;
+
+/// CPU vendor, as reported by [`mvendorid`]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Vendor {
+    SiFive,
+    THead,
+    Andes,
+    /// Vendor not known by this crate. It contains the raw `mvendorid` value.
+    Other(usize),
+    /// `mvendorid` is not implemented (i.e., it reads as zero)
+    Unknown,
+}
+
+/// Features of the current hart
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CpuFeatures {
+    /// Raw value of `misa` (`0` if not implemented)
+    pub misa: usize,
+    /// CPU vendor
+    pub vendor: Vendor,
+    /// Address generation instructions (Zba)
+    pub zba: bool,
+    /// Basic bit manipulation (Zbb)
+    pub zbb: bool,
+    /// Single-bit instructions (Zbs)
+    pub zbs: bool,
+    /// Integer conditional operations (Zicond)
+    pub zicond: bool,
+    /// Cache-block management instructions (Zicbom)
+    pub zicbom: bool,
+    /// T-Head vendor cache maintenance instructions (XTheadCmo)
+    pub xtheadcmo: bool,
+}
+
+impl Vendor {
+    /// Identifies the vendor of the current hart from [`mvendorid`].
+    #[inline]
+    pub fn read() -> Self {
+        match mvendorid::read().map(|id| id.bits()) {
+            Some(SIFIVE) => Self::SiFive,
+            Some(THEAD) => Self::THead,
+            Some(ANDES) => Self::Andes,
+            Some(id) => Self::Other(id),
+            None => Self::Unknown,
+        }
+    }
+}
+
+impl CpuFeatures {
+    /// Returns `true` if `misa` reports the single-letter extension (e.g., `'A'`).
+    #[inline]
+    pub fn has_extension(&self, extension: char) -> bool {
+        let extension = extension.to_ascii_uppercase();
+        if !extension.is_ascii_uppercase() {
+            return false;
+        }
+        self.misa & (1 << (extension as u8 - b'A')) != 0
+    }
+}
+
+/// Executes a 32-bit instruction with a temporary trap handler.
+/// It returns `false` if the instruction raised an illegal instruction exception.
+#[cfg(riscv)]
+macro_rules! probe {
+    ($insn:literal) => {{
+        let cause: usize;
+        core::arch::asm!(
+            "la {tmp}, 3f",
+            "csrrw {mtvec}, mtvec, {tmp}",
+            "li {cause}, 0",
+            concat!(".word ", $insn),
+            "j 4f",
+            ".align 2",
+            "3:",
+            // Trap handler: report mcause + 1 and skip the probed instruction
+            "csrr {cause}, mcause",
+            "addi {cause}, {cause}, 1",
+            "csrr {tmp}, mepc",
+            "addi {tmp}, {tmp}, 4",
+            "csrw mepc, {tmp}",
+            "mret",
+            "4:",
+            "csrw mtvec, {mtvec}",
+            tmp = out(reg) _,
+            mtvec = out(reg) _,
+            cause = out(reg) cause,
+        );
+        // Illegal instruction exception (code 2)
+        cause != 2 + 1
+    }};
+}
+
+/// Detects the features of the current hart.
+///
+/// # Safety
+///
+/// It must be executed in M-mode. It temporarily replaces `mtvec`, so no other trap can be handled while probing.
+#[inline]
+pub unsafe fn detect() -> CpuFeatures {
+    match () {
+        #[cfg(riscv)]
+        () => {
+            let misa = misa::read().map_or(0, |misa| misa.bits());
+            let vendor = Vendor::read();
+            interrupt::free(|| CpuFeatures {
+                misa,
+                vendor,
+                // sh1add zero, zero, zero
+                zba: probe!("0x20002033"),
+                // andn zero, zero, zero
+                zbb: probe!("0x40007033"),
+                // bset zero, zero, zero
+                zbs: probe!("0x28001033"),
+                // czero.eqz zero, zero, zero
+                zicond: probe!("0x0E005033"),
+                // cbo.clean 0(sp)
+                zicbom: probe!("0x0011200F"),
+                // T-Head cores implement their own cache instructions instead of Zicbom
+                xtheadcmo: vendor == Vendor::THead,
+            })
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
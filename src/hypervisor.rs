@@ -0,0 +1,115 @@
+//! Hypervisor utilities
+//!
+//! [`forward_to_vs`] injects a trap taken in HS-mode into the guest running in VS-mode,
+//! following the trap-injection rules of the hypervisor extension. After it, `sret` enters
+//! the trap handler of the guest as if the guest had taken the trap itself.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::hypervisor::{forward_to_vs, TrapInfo};
+//!
+//! // HS-mode trap handler
+//! fn trap_handler() {
+//!     let trap = TrapInfo::read();
+//!     // the guest must handle its own page faults
+//!     unsafe { forward_to_vs(&trap) };
+//!     // restore the guest context and execute sret
+//! }
+//! ```
+use crate::register::{hstatus, scause, sepc, sstatus, vscause, vsepc, vsstatus, vstval, vstvec};
+use crate::trap::SupervisorReport;
+
+/// Description of a trap to be forwarded to VS-mode.
+#[derive(Clone, Copy, Debug)]
+pub struct TrapInfo {
+    /// Raw cause of the trap, with the `scause` layout.
+    pub cause: usize,
+    /// Address of the guest instruction that was interrupted or raised the exception.
+    pub epc: usize,
+    /// Exception-specific information (e.g., the faulting address).
+    pub tval: usize,
+    /// Privilege of the guest when the trap was taken (VS-mode or VU-mode).
+    pub spp: sstatus::SPP,
+}
+
+impl TrapInfo {
+    /// Reads the HS-mode trap CSRs of a trap taken from VS-mode or VU-mode.
+    #[inline]
+    pub fn read() -> Self {
+        Self::from(&SupervisorReport::read())
+    }
+}
+
+impl From<&SupervisorReport> for TrapInfo {
+    #[inline]
+    fn from(report: &SupervisorReport) -> Self {
+        Self {
+            cause: report.scause.bits(),
+            epc: report.sepc,
+            tval: report.stval,
+            spp: report.sstatus.spp(),
+        }
+    }
+}
+
+/// Forwards a trap to the guest running in VS-mode.
+///
+/// Namely, it performs the following operations, as VS-mode hardware would do on a trap:
+///
+/// - Writes `vscause`, `vsepc` and `vstval`. VS-level interrupts (codes 2, 6 and 10 in `scause`)
+///   are translated to the S-level codes the guest expects (1, 5 and 9, respectively).
+/// - Sets `vsstatus.SPP` to the privilege of the guest, copies `vsstatus.SIE` to `vsstatus.SPIE`,
+///   and clears `vsstatus.SIE`.
+/// - Sets `sepc` to the trap handler of the guest, according to `vstvec`.
+///   Interrupts jump to `BASE + 4 * cause` in vectored mode.
+/// - Sets `hstatus.SPV` and `sstatus.SPP`, so the next `sret` returns to VS-mode.
+///
+/// # Safety
+///
+/// It must be called from HS-mode, with interrupts disabled.
+/// Nothing may change `sepc`, `hstatus` or `sstatus` between this function and `sret`.
+#[inline]
+pub unsafe fn forward_to_vs(trap: &TrapInfo) {
+    let cause = guest_cause(trap.cause);
+    vscause::write(cause);
+    vsepc::write(trap.epc);
+    vstval::write(trap.tval);
+
+    let mut status = vsstatus::read().bits();
+    let sie = status & (1 << 1) != 0;
+    status &= !((1 << 1) | (1 << 5) | (1 << 8));
+    if sie {
+        status |= 1 << 5;
+    }
+    if trap.spp == sstatus::SPP::Supervisor {
+        status |= 1 << 8;
+    }
+    vsstatus::write(status);
+
+    let vstvec = vstvec::read();
+    let cause = scause::Scause::from_bits(cause);
+    let handler = match vstvec.trap_mode() {
+        Some(vstvec::TrapMode::Vectored) if cause.is_interrupt() => {
+            vstvec.address() + 4 * cause.code()
+        }
+        _ => vstvec.address(),
+    };
+    sepc::write(handler);
+
+    hstatus::set_spv();
+    sstatus::set_spp(sstatus::SPP::Supervisor);
+}
+
+/// Translates a cause of `scause` to the cause seen by the guest in `vscause`.
+///
+/// VS-level interrupts are reported to HS-mode with codes 2, 6 and 10,
+/// but the guest sees them as S-level interrupts with codes 1, 5 and 9.
+#[inline]
+fn guest_cause(cause: usize) -> usize {
+    let scause = scause::Scause::from_bits(cause);
+    match (scause.is_interrupt(), scause.code()) {
+        (true, 2 | 6 | 10) => cause - 1,
+        _ => cause,
+    }
+}
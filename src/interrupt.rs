@@ -1,7 +1,9 @@
 //! Interrupts
 
 // NOTE: Adapted from cortex-m/src/interrupt.rs
-use crate::register::mstatus;
+use crate::register::{mcause, mie, mip, mstatus};
+use core::cell::Cell;
+use critical_section::Mutex;
 
 /// Disables all interrupts in the current hart.
 #[inline]
@@ -61,3 +63,292 @@ where
 
     r
 }
+
+/// Reason that woke up the current hart from a `WFI` instruction.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WakeReason {
+    /// An enabled interrupt is pending. Interrupts were disabled, so its trap was not taken yet.
+    /// It contains the interrupt code (i.e., its bit in `mip`).
+    Pending(usize),
+    /// An interrupt trap was taken and serviced before resuming.
+    /// It contains the interrupt code reported by `mcause`.
+    Serviced(usize),
+    /// No interrupt could be identified. `WFI` may complete for implementation-specific reasons.
+    Spurious,
+}
+
+impl WakeReason {
+    /// Returns the code of the interrupt that woke up the hart, if any.
+    #[inline]
+    pub fn code(&self) -> Option<usize> {
+        match *self {
+            Self::Pending(code) | Self::Serviced(code) => Some(code),
+            Self::Spurious => None,
+        }
+    }
+}
+
+/// Executes a `WFI` instruction and reports which interrupt woke up the hart.
+///
+/// If `masked` is `true`, interrupts are globally disabled while waiting. `WFI` still completes
+/// when an enabled interrupt becomes pending, but its trap is not taken until interrupts are
+/// enabled again. In this case, the reason is identified from `mip` and `mie`, picking the pending
+/// interrupt with the highest standard priority. On return, `mstatus.MIE` is restored.
+///
+/// If `masked` is `false`, the trap is taken before `WFI` resumes, and the reason is identified
+/// from `mcause`. To detect spurious wake-ups, `mcause` is cleared before waiting.
+/// If interrupts are already globally disabled (i.e., `mstatus.MIE` is clear), no trap can be taken,
+/// so the function behaves as if `masked` were `true`.
+///
+/// # Safety
+///
+/// If `masked` is `false` and `mstatus.MIE` is set, `mcause` is overwritten.
+#[inline]
+pub unsafe fn wfi_and_identify(masked: bool) -> WakeReason {
+    if masked || !mstatus::read().mie() {
+        free(|| {
+            crate::asm::wfi();
+            let pending = mip::read().bits() & mie::read().bits();
+            match highest_priority(pending) {
+                Some(code) => WakeReason::Pending(code),
+                None => WakeReason::Spurious,
+            }
+        })
+    } else {
+        mcause::write(0);
+        crate::asm::wfi();
+        let mcause = mcause::read();
+        match mcause.is_interrupt() {
+            true => WakeReason::Serviced(mcause.code()),
+            false => WakeReason::Spurious,
+        }
+    }
+}
+
+/// Puts the hart to sleep with `WFI` unless there is pending work, without losing wake-ups.
+///
+/// Interrupts are globally disabled while `has_work` is checked and while waiting. Thus, an interrupt
+/// that creates new work cannot be serviced between the check and `WFI`, which would otherwise
+/// put the hart to sleep with pending work. `WFI` still completes when an enabled interrupt becomes
+/// pending, and its trap is taken as soon as interrupts are enabled again.
+///
+/// It returns `true` if the hart executed `WFI`, and `false` if `has_work` returned `true`.
+/// On return, interrupts are enabled.
+///
+/// # Safety
+///
+/// - Do not call this function inside a critical section.
+///
+/// # Example
+///
+/// ``` no_run
+/// use core::sync::atomic::{AtomicBool, Ordering};
+///
+/// static EVENT: AtomicBool = AtomicBool::new(false);
+///
+/// loop {
+///     unsafe { riscv::interrupt::idle_until_interrupt(|| EVENT.load(Ordering::Relaxed)) };
+///     if EVENT.swap(false, Ordering::Relaxed) {
+///         // process the event here
+///     }
+/// }
+/// ```
+#[inline]
+pub unsafe fn idle_until_interrupt<F: FnOnce() -> bool>(has_work: F) -> bool {
+    disable();
+    let sleep = !has_work();
+    if sleep {
+        crate::asm::wfi();
+    }
+    enable();
+    sleep
+}
+
+/// Returns the code of the pending interrupt with the highest priority.
+///
+/// Standard interrupts follow the priority order of the privileged specification.
+/// Platform-specific interrupts come next, with lower codes taking precedence.
+#[inline]
+fn highest_priority(pending: usize) -> Option<usize> {
+    // MEI, MSI, MTI, SEI, SSI, STI, LCOFI
+    const PRIORITY: [usize; 7] = [11, 3, 7, 9, 1, 5, 13];
+    PRIORITY
+        .iter()
+        .copied()
+        .find(|&code| pending & (1 << code) != 0)
+        .or_else(|| match pending {
+            0 => None,
+            _ => Some(pending.trailing_zeros() as usize),
+        })
+}
+
+/// Interrupt handler that can be installed in a [`HandlerTable`].
+pub type Handler = fn();
+
+/// Table of interrupt handlers that can be installed at runtime.
+///
+/// Each entry stores an optional handler for the interrupt with the same code.
+/// The trap handler can call [`HandlerTable::dispatch`] with the interrupt code to run it.
+///
+/// # Note
+///
+/// Accesses to the table are executed within a critical section via [`critical_section::with`].
+/// Multi-hart targets must use a `critical-section` implementation that also synchronizes the harts.
+///
+/// # Example
+///
+/// ``` no_run
+/// use riscv::{interrupt::HandlerTable, register::mcause};
+///
+/// static HANDLERS: HandlerTable<16> = HandlerTable::new();
+///
+/// fn machine_timer() { /* ... */ }
+///
+/// fn main() {
+///     HANDLERS.register(7, machine_timer);
+/// }
+///
+/// // Called from the trap handler
+/// fn machine_interrupt() {
+///     let code = mcause::read().code();
+///     if !HANDLERS.dispatch(code) {
+///         panic!("unhandled interrupt {}", code);
+///     }
+/// }
+/// ```
+pub struct HandlerTable<const N: usize> {
+    handlers: Mutex<Cell<[Option<Handler>; N]>>,
+}
+
+impl<const N: usize> HandlerTable<N> {
+    /// Creates a new table with no handlers.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            handlers: Mutex::new(Cell::new([None; N])),
+        }
+    }
+
+    /// Returns the number of entries of the table.
+    #[inline]
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Checks if the table has no entries.
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the handler of an interrupt, if any.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `code` is not lower than `N`.
+    #[inline]
+    pub fn handler(&self, code: usize) -> Option<Handler> {
+        self.with_entry(code, |entry| entry.get())
+    }
+
+    /// Installs the handler of an interrupt and returns the previous one, if any.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `code` is not lower than `N`.
+    #[inline]
+    pub fn register(&self, code: usize, handler: Handler) -> Option<Handler> {
+        self.with_entry(code, |entry| entry.replace(Some(handler)))
+    }
+
+    /// Removes the handler of an interrupt and returns it, if any.
+    ///
+    /// # Panics
+    ///
+    /// It panics if `code` is not lower than `N`.
+    #[inline]
+    pub fn unregister(&self, code: usize) -> Option<Handler> {
+        self.with_entry(code, |entry| entry.take())
+    }
+
+    /// Runs the handler of an interrupt. It returns `false` if there is no handler for `code`.
+    /// In that case, the interrupt is reported via [`report_spurious`].
+    ///
+    /// The handler is fetched within a critical section, but it runs outside of it.
+    #[inline]
+    pub fn dispatch(&self, code: usize) -> bool {
+        match (code < N).then(|| self.handler(code)).flatten() {
+            Some(handler) => {
+                handler();
+                true
+            }
+            None => {
+                report_spurious(Spurious::Unhandled(code));
+                false
+            }
+        }
+    }
+
+    /// Calls `f` with the entry of an interrupt within a critical section.
+    #[inline]
+    fn with_entry<R>(&self, code: usize, f: impl FnOnce(&Cell<Option<Handler>>) -> R) -> R {
+        assert!(code < N);
+        critical_section::with(|cs| {
+            let handlers: &Cell<[Option<Handler>]> = self.handlers.borrow(cs);
+            f(&handlers.as_slice_of_cells()[code])
+        })
+    }
+}
+
+impl<const N: usize> Default for HandlerTable<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Kind of spurious interrupt.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Spurious {
+    /// A PLIC claim returned no interrupt source (i.e., source 0) for the given context.
+    Claim {
+        /// PLIC context that was claimed.
+        context: usize,
+    },
+    /// There is no handler for the given interrupt code (e.g., in a [`HandlerTable`]).
+    Unhandled(usize),
+}
+
+/// Callback for spurious interrupts.
+pub type SpuriousHandler = fn(Spurious);
+
+static SPURIOUS_HANDLER: Mutex<Cell<Option<SpuriousHandler>>> = Mutex::new(Cell::new(None));
+
+/// Installs the callback for spurious interrupts and returns the previous one, if any.
+///
+/// By default, spurious interrupts are silently ignored.
+///
+/// # Note
+///
+/// The callback is shared by all the harts. Install it before enabling interrupts.
+#[inline]
+pub fn set_spurious_handler(handler: SpuriousHandler) -> Option<SpuriousHandler> {
+    critical_section::with(|cs| SPURIOUS_HANDLER.borrow(cs).replace(Some(handler)))
+}
+
+/// Removes the callback for spurious interrupts and returns it, if any.
+#[inline]
+pub fn clear_spurious_handler() -> Option<SpuriousHandler> {
+    critical_section::with(|cs| SPURIOUS_HANDLER.borrow(cs).take())
+}
+
+/// Reports a spurious interrupt to the callback installed with [`set_spurious_handler`], if any.
+///
+/// Interrupt dispatchers call it when an interrupt cannot be attributed to any handler.
+/// The callback is fetched within a critical section, but it runs outside of it.
+#[inline]
+pub fn report_spurious(spurious: Spurious) {
+    if let Some(handler) = critical_section::with(|cs| SPURIOUS_HANDLER.borrow(cs).get()) {
+        handler(spurious);
+    }
+}
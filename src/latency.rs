@@ -0,0 +1,132 @@
+//! Interrupt latency measurement
+//!
+//! # Note
+//!
+//! This module requires the `latency` feature.
+//!
+//! [`Latency`] timestamps (using `mcycle`) the moment an interrupt source is pended
+//! and the moment its handler starts. For each source, it keeps the minimum and maximum latency
+//! and a histogram of the measured latencies, all of them in CPU cycles.
+//!
+//! Software must call [`Latency::pend`] right before pending the interrupt (e.g., when triggering
+//! a software interrupt or a peripheral event) and [`Latency::enter`] at the beginning of the handler.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::latency::Latency;
+//!
+//! // 4 interrupt sources, 8 histogram bins of 16 cycles each.
+//! static LATENCY: Latency<4, 8> = Latency::new(16);
+//!
+//! fn trigger_source_1() {
+//!     LATENCY.pend(1);
+//!     // pend interrupt source 1 here
+//! }
+//!
+//! fn source_1_handler() {
+//!     LATENCY.enter(1);
+//!     // handle the interrupt here
+//! }
+//! ```
+use crate::register::mcycle;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Latency statistics of an interrupt source.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats<const BINS: usize> {
+    /// Number of measured latencies.
+    pub count: u32,
+    /// Minimum latency (in cycles). It is `u64::MAX` if no latency has been measured.
+    pub min: u64,
+    /// Maximum latency (in cycles).
+    pub max: u64,
+    /// Histogram of latencies. The last bin also counts all the latencies above its range.
+    pub histogram: [u32; BINS],
+}
+
+impl<const BINS: usize> Stats<BINS> {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            min: u64::MAX,
+            max: 0,
+            histogram: [0; BINS],
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Entry<const BINS: usize> {
+    pended: Option<u64>,
+    stats: Stats<BINS>,
+}
+
+/// Latency measurements for `N` interrupt sources with a histogram of `BINS` bins each.
+pub struct Latency<const N: usize, const BINS: usize> {
+    bin_width: u64,
+    entries: Mutex<RefCell<[Entry<BINS>; N]>>,
+}
+
+impl<const N: usize, const BINS: usize> Latency<N, BINS> {
+    /// Creates a new latency measurement set.
+    /// Each histogram bin covers `bin_width` cycles.
+    pub const fn new(bin_width: u64) -> Self {
+        assert!(bin_width > 0, "bin width must be greater than 0");
+        assert!(BINS > 0, "at least one histogram bin is required");
+        Self {
+            bin_width,
+            entries: Mutex::new(RefCell::new(
+                [Entry {
+                    pended: None,
+                    stats: Stats::new(),
+                }; N],
+            )),
+        }
+    }
+
+    /// Records the moment the interrupt source is pended.
+    #[inline]
+    pub fn pend(&self, source: usize) {
+        let now = mcycle::read64();
+        critical_section::with(|cs| {
+            self.entries.borrow(cs).borrow_mut()[source].pended = Some(now);
+        });
+    }
+
+    /// Records the moment the handler of the interrupt source starts.
+    /// It returns the measured latency, or [`None`] if the source was not pended through [`Self::pend`].
+    #[inline]
+    pub fn enter(&self, source: usize) -> Option<u64> {
+        let now = mcycle::read64();
+        critical_section::with(|cs| {
+            let entry = &mut self.entries.borrow(cs).borrow_mut()[source];
+            let latency = now.wrapping_sub(entry.pended.take()?);
+            let stats = &mut entry.stats;
+            stats.count = stats.count.saturating_add(1);
+            stats.min = stats.min.min(latency);
+            stats.max = stats.max.max(latency);
+            let bin = (latency / self.bin_width).min(BINS as u64 - 1) as usize;
+            stats.histogram[bin] = stats.histogram[bin].saturating_add(1);
+            Some(latency)
+        })
+    }
+
+    /// Returns the latency statistics of an interrupt source.
+    #[inline]
+    pub fn stats(&self, source: usize) -> Stats<BINS> {
+        critical_section::with(|cs| self.entries.borrow(cs).borrow()[source].stats)
+    }
+
+    /// Clears the statistics of all the interrupt sources.
+    #[inline]
+    pub fn reset(&self) {
+        critical_section::with(|cs| {
+            for entry in self.entries.borrow(cs).borrow_mut().iter_mut() {
+                entry.pended = None;
+                entry.stats = Stats::new();
+            }
+        });
+    }
+}
@@ -29,11 +29,34 @@
 #![allow(clippy::missing_safety_doc)]
 
 pub mod asm;
+pub mod atomic;
+#[cfg(any(target_feature = "a", not(riscv)))]
+pub mod barrier;
+pub mod cache;
+pub mod debug;
+pub mod deferred;
 pub mod delay;
+pub mod features;
+pub mod hypervisor;
 pub mod interrupt;
-#[cfg(any(feature = "plic"))]
+#[cfg(feature = "latency")]
+pub mod latency;
+#[cfg(any(
+    feature = "clint",
+    feature = "eclic",
+    feature = "pfic",
+    feature = "plic",
+    feature = "vexriscv"
+))]
 pub mod peripheral;
 pub mod register;
+#[cfg(feature = "rand_core")]
+pub mod rng;
+pub mod snapshot;
+#[cfg(feature = "stats")]
+pub mod stats;
+pub mod time;
+pub mod trap;
 
 #[macro_use]
 mod macros;
@@ -61,17 +61,17 @@ macro_rules! singleton {
 /// This macro expects 5 arguments:
 ///
 /// - `PLIC`: name of the PLIC context interface structure to be created.
-/// We recommend to leave `PLIC` for context 0 and `PLICx` for the remaining contexts.
+///   We recommend to leave `PLIC` for context 0 and `PLICx` for the remaining contexts.
 ///
 /// - `BASE`: base address of the PLIC peripheral of the target.
 ///
 /// - `CONTEXT`: context number assigned to the PLIC interface.
 ///
 /// - `INTERRUPT`: enum type of the external interruptions of the target.
-/// This type must implement the [`crate::peripheral::plic::InterruptNumber`] trait.
+///   This type must implement the [`crate::peripheral::plic::InterruptNumber`] trait.
 ///
 /// - `PRIORITY`: enum type of the priority levels supported by the target.
-/// This type must implement the [`crate::peripheral::plic::PriorityNumber`] trait.
+///   This type must implement the [`crate::peripheral::plic::PriorityNumber`] trait.
 ///
 /// # Note
 ///
@@ -106,6 +106,28 @@ macro_rules! plic_context {
                 $crate::peripheral::PLIC::<$BASE, $CONTEXT>::disable();
             }
 
+            /// Enables supervisor external interrupts.
+            #[inline(always)]
+            pub fn enable_supervisor() {
+                $crate::peripheral::PLIC::<$BASE, $CONTEXT>::enable_supervisor();
+            }
+
+            /// Disables supervisor external interrupts.
+            #[inline(always)]
+            pub fn disable_supervisor() {
+                $crate::peripheral::PLIC::<$BASE, $CONTEXT>::disable_supervisor();
+            }
+
+            /// Sets the priority threshold and enables supervisor external interrupts.
+            ///
+            /// # Safety
+            ///
+            /// Unmasking an interrupt source can break mask-based critical sections.
+            #[inline(always)]
+            pub unsafe fn init_supervisor(&mut self, threshold: $PRIORITY) {
+                self.context.init_supervisor(threshold);
+            }
+
             /// Returns the priority level associated to a given interrupt source.
             #[inline(always)]
             pub fn priority(source: $INTERRUPT) -> $PRIORITY {
@@ -201,6 +223,13 @@ macro_rules! plic_context {
                 $crate::peripheral::PLIC::<$BASE, $CONTEXT>::complete(source);
             }
 
+            /// Serves all the pending interrupts of the PLIC context.
+            /// It returns the number of served interrupts.
+            #[inline(always)]
+            pub fn service<F: FnMut($INTERRUPT)>(handler: F) -> usize {
+                $crate::peripheral::PLIC::<$BASE, $CONTEXT>::service(handler)
+            }
+
             /// Resets the PLIC peripherals.
             ///
             /// # Safety
@@ -213,3 +242,317 @@ macro_rules! plic_context {
         }
     };
 }
+
+/// Macro to declare the whole interrupt map of an ECLIC in one place.
+///
+/// It generates an unsafe function that configures the number of level bits and,
+/// for every listed interrupt source, its trigger type, hardware vectoring and level.
+/// The level assignment is checked at compile time with [`crate::peripheral::eclic::validate_levels`].
+/// Optionally (with `table: NAME`), it also generates a constant table with the interrupt number and level of every source,
+/// so other compile-time checks (e.g., preemption constraints) can reuse it.
+///
+/// The entries use the variant names of `INTERRUPT`, which must be a fieldless enum
+/// that implements [`crate::peripheral::InterruptNumber`]. `trigger` is a variant of
+/// [`crate::peripheral::eclic::Trigger`]. The generated function does not enable the interrupt sources.
+///
+/// # Note
+///
+/// This macro requires the `eclic` feature to be active.
+/// It targets the Nuclei ECLIC, which follows the `clicintattr`/`clicintctl` layout of the CLIC draft.
+///
+/// # Example
+///
+/// ``` no_run
+/// # use riscv::peripheral::{InterruptNumber, ECLIC};
+/// # #[derive(Clone, Copy)]
+/// # pub enum Interrupt { Uart0 = 19, Timer0 = 20 }
+/// # unsafe impl InterruptNumber for Interrupt {
+/// #     const MAX_INTERRUPT_NUMBER: u16 = 20;
+/// #     fn number(self) -> u16 { self as u16 }
+/// #     fn try_from(value: u16) -> Result<Self, u16> {
+/// #         match value { 19 => Ok(Self::Uart0), 20 => Ok(Self::Timer0), _ => Err(value) }
+/// #     }
+/// # }
+/// riscv::clic_config! {
+///     /// Configures the interrupt sources of the application
+///     pub unsafe fn configure_interrupts for Interrupt, nlbits: 2, table: pub LEVELS;
+///     Uart0 { level: 3, trigger: PositiveEdge, vectored: true },
+///     Timer0 { level: 1, trigger: Level, vectored: false },
+/// }
+///
+/// // the UART interrupt must preempt the timer interrupt
+/// const _: () = riscv::peripheral::eclic::validate_levels(
+///     LEVELS,
+///     2,
+///     &[(Interrupt::Uart0 as u16, Interrupt::Timer0 as u16)],
+/// );
+///
+/// let mut eclic = ECLIC::<0xD200_0000>::new();
+/// unsafe { configure_interrupts(&mut eclic) };
+/// ```
+///
+/// ``` compile_fail
+/// # use riscv::peripheral::{InterruptNumber, ECLIC};
+/// # #[derive(Clone, Copy)]
+/// # pub enum Interrupt { Uart0 = 19 }
+/// # unsafe impl InterruptNumber for Interrupt {
+/// #     const MAX_INTERRUPT_NUMBER: u16 = 19;
+/// #     fn number(self) -> u16 { self as u16 }
+/// #     fn try_from(value: u16) -> Result<Self, u16> {
+/// #         match value { 19 => Ok(Self::Uart0), _ => Err(value) }
+/// #     }
+/// # }
+/// riscv::clic_config! {
+///     pub unsafe fn configure_interrupts for Interrupt, nlbits: 2;
+///     // with 2 level bits, levels must be lower than 4
+///     Uart0 { level: 4, trigger: Level, vectored: false },
+/// }
+/// ```
+#[cfg(feature = "eclic")]
+#[macro_export]
+macro_rules! clic_config {
+    (
+        $(#[$attr:meta])*
+        $vis:vis unsafe fn $name:ident for $INTERRUPT:ident, nlbits: $nlbits:literal, table: $table_vis:vis $TABLE:ident;
+        $($source:ident { level: $level:literal, trigger: $trigger:ident, vectored: $vectored:literal $(,)? }),* $(,)?
+    ) => {
+        /// Interrupt number and level of every configured interrupt source.
+        $table_vis const $TABLE: &[(u16, u8)] = &[$(($INTERRUPT::$source as u16, $level)),*];
+
+        $crate::clic_config! {
+            $(#[$attr])*
+            $vis unsafe fn $name for $INTERRUPT, nlbits: $nlbits;
+            $($source { level: $level, trigger: $trigger, vectored: $vectored }),*
+        }
+    };
+    (
+        $(#[$attr:meta])*
+        $vis:vis unsafe fn $name:ident for $INTERRUPT:ident, nlbits: $nlbits:literal;
+        $($source:ident { level: $level:literal, trigger: $trigger:ident, vectored: $vectored:literal $(,)? }),* $(,)?
+    ) => {
+        $(#[$attr])*
+        ///
+        /// # Safety
+        ///
+        /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+        /// Additionally, changing levels can break level-based critical sections.
+        $vis unsafe fn $name<const BASE: usize>(eclic: &mut $crate::peripheral::ECLIC<BASE>) {
+            const _: () = $crate::peripheral::eclic::validate_levels(
+                &[$(($INTERRUPT::$source as u16, $level)),*],
+                $nlbits,
+                &[],
+            );
+            eclic.set_nlbits($nlbits);
+            $(
+                eclic.set_trigger($INTERRUPT::$source, $crate::peripheral::eclic::Trigger::$trigger);
+                eclic.set_vectored($INTERRUPT::$source, $vectored);
+                eclic.set_level($INTERRUPT::$source, $level);
+            )*
+        }
+    };
+}
+
+/// Macro to create the interfaces to the core peripherals of a platform in PACs.
+///
+/// It generates a type alias for every peripheral in the list, with the base addresses
+/// of the target, and a `Peripherals` structure that gathers all of them.
+/// Like in `svd2rust`-generated PACs, `Peripherals::take` returns the structure only once,
+/// and `Peripherals::steal` returns it unconditionally.
+///
+/// The list of peripherals must follow this order, and every entry is optional:
+///
+/// - `CLINT { base: BASE },`: CLINT peripheral with base address `BASE`.
+///   It requires the `clint` feature.
+///
+/// - `ECLIC { base: BASE },`: Nuclei ECLIC peripheral with base address `BASE`.
+///   It requires the `eclic` feature.
+///
+/// - `PFIC { base: BASE },`: WCH PFIC peripheral with base address `BASE`.
+///   It requires the `pfic` feature.
+///
+/// - `PLIC { base: BASE, context: CONTEXT },`: context `CONTEXT` of the PLIC peripheral
+///   with base address `BASE`. It requires the `plic` feature.
+///
+/// `BASE` and `CONTEXT` are constant expressions, e.g., literals or `const` items.
+///
+/// # Note
+///
+/// `Peripherals::take` requires a `critical-section` implementation to be set.
+///
+/// # Example
+///
+/// ``` no_run
+/// riscv::riscv_peripherals! {
+///     CLINT { base: 0x0200_0000 },
+///     PLIC { base: 0x0C00_0000, context: 0 },
+/// }
+///
+/// let mut peripherals = Peripherals::take().unwrap();
+/// peripherals.CLINT.set_mtimecmp(0, CLINT::mtime() + 1_000);
+/// PLIC::enable();
+/// ```
+#[cfg(any(
+    feature = "clint",
+    feature = "eclic",
+    feature = "pfic",
+    feature = "plic"
+))]
+#[macro_export]
+macro_rules! riscv_peripherals {
+    (
+        $(CLINT { base: $CLINT_BASE:expr },)?
+        $(ECLIC { base: $ECLIC_BASE:expr },)?
+        $(PFIC { base: $PFIC_BASE:expr },)?
+        $(PLIC { base: $PLIC_BASE:expr, context: $PLIC_CONTEXT:expr },)?
+    ) => {
+        $(
+            /// Core-Local Interruptor (CLINT) peripheral.
+            pub type CLINT = $crate::peripheral::CLINT<{ $CLINT_BASE }>;
+        )?
+        $(
+            /// Nuclei Enhanced Core-Local Interrupt Controller (ECLIC) peripheral.
+            pub type ECLIC = $crate::peripheral::ECLIC<{ $ECLIC_BASE }>;
+        )?
+        $(
+            /// WCH Programmable Fast Interrupt Controller (PFIC) peripheral.
+            pub type PFIC = $crate::peripheral::PFIC<{ $PFIC_BASE }>;
+        )?
+        $(
+            /// Platform-Level Interrupt Controller (PLIC) context.
+            pub type PLIC = $crate::peripheral::PLIC<{ $PLIC_BASE }, { $PLIC_CONTEXT }>;
+        )?
+
+        /// Core peripherals of the platform.
+        #[allow(non_snake_case)]
+        pub struct Peripherals {
+            $(
+                /// Core-Local Interruptor (CLINT) peripheral.
+                pub CLINT: $crate::peripheral::CLINT<{ $CLINT_BASE }>,
+            )?
+            $(
+                /// Nuclei Enhanced Core-Local Interrupt Controller (ECLIC) peripheral.
+                pub ECLIC: $crate::peripheral::ECLIC<{ $ECLIC_BASE }>,
+            )?
+            $(
+                /// WCH Programmable Fast Interrupt Controller (PFIC) peripheral.
+                pub PFIC: $crate::peripheral::PFIC<{ $PFIC_BASE }>,
+            )?
+            $(
+                /// Platform-Level Interrupt Controller (PLIC) context.
+                pub PLIC: $crate::peripheral::PLIC<{ $PLIC_BASE }, { $PLIC_CONTEXT }>,
+            )?
+        }
+
+        static mut RISCV_PERIPHERALS_TAKEN: bool = false;
+
+        impl Peripherals {
+            /// Returns all the core peripherals *once*.
+            #[inline]
+            pub fn take() -> Option<Self> {
+                $crate::_export::critical_section::with(|_| {
+                    // SAFETY: the flag is only accessed within a critical section
+                    if unsafe { RISCV_PERIPHERALS_TAKEN } {
+                        None
+                    } else {
+                        Some(unsafe { Self::steal() })
+                    }
+                })
+            }
+
+            /// Unchecked version of [`Peripherals::take`].
+            ///
+            /// # Safety
+            ///
+            /// Each of the returned peripherals must be used at most once.
+            #[inline]
+            pub unsafe fn steal() -> Self {
+                RISCV_PERIPHERALS_TAKEN = true;
+                Self {
+                    $(CLINT: $crate::peripheral::CLINT::<{ $CLINT_BASE }>::new(),)?
+                    $(ECLIC: $crate::peripheral::ECLIC::<{ $ECLIC_BASE }>::new(),)?
+                    $(PFIC: $crate::peripheral::PFIC::<{ $PFIC_BASE }>::new(),)?
+                    $(PLIC: $crate::peripheral::PLIC::<{ $PLIC_BASE }, { $PLIC_CONTEXT }>::new(),)?
+                }
+            }
+        }
+    };
+}
+
+/// Assembly snippet that swaps `sp` and `mscratch`.
+///
+/// It expands to a string literal to be used within `asm!` or `global_asm!`.
+/// Use it as the first instruction of the trap handler to switch to the trap stack
+/// previously set with [`crate::register::mscratch::init_trap_stack`],
+/// and as the last instruction before `mret` to switch back to the interrupted stack.
+///
+/// # Note
+///
+/// Before swapping back, `sp` must be restored to the top of the trap stack.
+/// Otherwise, `mscratch` loses track of the trap stack and the next trap corrupts memory.
+/// Nested traps are not supported: interrupts must remain disabled while `sp` and `mscratch` are swapped.
+///
+/// # Example
+///
+/// ```
+/// macro_rules! trap_entry {
+///     () => {
+///         concat!(
+///             ".section .trap, \"ax\"\n",
+///             ".global _trap_entry\n",
+///             "_trap_entry:\n",
+///             riscv::swap_sp_mscratch!(), "\n",
+///             "# save registers on the trap stack and call the trap handler\n",
+///             "# ...\n",
+///             "# restore registers, leaving sp at the top of the trap stack\n",
+///             riscv::swap_sp_mscratch!(), "\n",
+///             "mret\n",
+///         )
+///     };
+/// }
+///
+/// #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+/// core::arch::global_asm!(trap_entry!());
+///
+/// assert_eq!(trap_entry!().matches("csrrw sp, mscratch, sp\n").count(), 2);
+/// ```
+#[macro_export]
+macro_rules! swap_sp_mscratch {
+    () => {
+        "csrrw sp, mscratch, sp"
+    };
+}
+
+/// Assembly snippet that swaps `sp` and `sscratch`.
+///
+/// It expands to a string literal to be used within `asm!` or `global_asm!`.
+/// It is the S-mode counterpart of [`crate::swap_sp_mscratch`].
+#[macro_export]
+macro_rules! swap_sp_sscratch {
+    () => {
+        "csrrw sp, sscratch, sp"
+    };
+}
+
+/// Installs an [`ExceptionHandler`](crate::trap::ExceptionHandler) implementation as the exception handler.
+///
+/// It exports the `ExceptionHandler` symbol expected by runtime crates such as `riscv-rt`.
+/// The trap frame argument is ignored, as the handler gathers the trap CSRs itself.
+///
+/// # Example
+///
+/// ``` no_run
+/// struct Handler;
+///
+/// impl riscv::trap::ExceptionHandler for Handler {}
+///
+/// riscv::exception_handler!(Handler);
+/// ```
+#[macro_export]
+macro_rules! exception_handler {
+    ($handler:ty) => {
+        #[export_name = "ExceptionHandler"]
+        pub extern "C" fn __riscv_exception_handler(_trap_frame: *const u8) {
+            $crate::trap::handle_exception::<$handler>();
+        }
+    };
+}
@@ -1,10 +1,188 @@
 //! RISC-V peripherals
 use core::marker::PhantomData;
 
+// Core-Local Interruptor
+#[cfg(feature = "clint")]
+pub mod clint;
+
+// Nuclei Enhanced Core-Local Interrupt Controller
+#[cfg(feature = "eclic")]
+pub mod eclic;
+
+// WCH Programmable Fast Interrupt Controller
+#[cfg(feature = "pfic")]
+pub mod pfic;
+
 // Platform-Level Interrupt Controller
 #[cfg(feature = "plic")]
 pub mod plic;
 
+// Raw register access
+pub mod reg;
+
+// VexRiscv external interrupt controller
+#[cfg(feature = "vexriscv")]
+pub mod vexriscv;
+
+// QEMU `virt` machine presets
+#[cfg(feature = "qemu-virt")]
+pub mod qemu_virt;
+
+/// Interface for the Nuclei ECLIC peripheral.
+///
+/// # Note
+///
+/// This structure requires the `eclic` feature.
+///
+/// The base address of the ECLIC depends on the platform (e.g., `0xD200_0000` in the GD32VF103).
+/// Thus, we use const generics to map an ECLIC to the desired memory location.
+///
+/// The base address is validated at compile time. It must be non-null, 4-byte aligned,
+/// and the whole register block must fit in the address space.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg(feature = "eclic")]
+#[derive(Default)]
+pub struct ECLIC<const BASE: usize> {
+    _marker: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "eclic")]
+impl<const BASE: usize> ECLIC<BASE> {
+    /// Pointer to the register block
+    pub const PTR: *const self::eclic::RegisterBlock = {
+        assert!(BASE != 0, "ECLIC base address must not be null");
+        assert!(BASE % 4 == 0, "ECLIC base address must be 4-byte aligned");
+        assert!(
+            BASE.checked_add(core::mem::size_of::<self::eclic::RegisterBlock>())
+                .is_some(),
+            "ECLIC register block must fit in the address space"
+        );
+        BASE as *const _
+    };
+
+    /// Creates a new interface for the ECLIC peripheral. PACs can use this
+    /// function to add an ECLIC interface to their `Peripherals` struct.
+    pub const fn new() -> Self {
+        // Force the compile-time validation of BASE
+        let _ = Self::PTR;
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Interface for the CLINT peripheral.
+///
+/// # Note
+///
+/// This structure requires the `clint` feature.
+///
+/// The RISC-V standard does not specify a fixed location for the CLINT.
+/// Thus, we use const generics to map a CLINT to the desired memory location.
+/// Each platform must specify the base address of the CLINT on the platform.
+///
+/// The base address is validated at compile time. It must be non-null, 8-byte aligned,
+/// and the whole register block must fit in the address space.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg(feature = "clint")]
+#[derive(Default)]
+pub struct CLINT<const BASE: usize> {
+    _marker: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "clint")]
+impl<const BASE: usize> CLINT<BASE> {
+    /// Pointer to the register block
+    pub const PTR: *const self::clint::RegisterBlock = {
+        assert!(BASE != 0, "CLINT base address must not be null");
+        assert!(BASE % 8 == 0, "CLINT base address must be 8-byte aligned");
+        assert!(
+            BASE.checked_add(core::mem::size_of::<self::clint::RegisterBlock>())
+                .is_some(),
+            "CLINT register block must fit in the address space"
+        );
+        BASE as *const _
+    };
+
+    /// Creates a new interface for the CLINT peripheral. PACs can use this
+    /// function to add a CLINT interface to their `Peripherals` struct.
+    pub const fn new() -> Self {
+        // Force the compile-time validation of BASE
+        let _ = Self::PTR;
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Interface for the WCH PFIC peripheral.
+///
+/// # Note
+///
+/// This structure requires the `pfic` feature.
+///
+/// The PFIC is usually located at `0xE000_E000` in CH32V devices.
+/// We still use const generics to map the PFIC to the desired memory location.
+///
+/// The base address is validated at compile time. It must be non-null, 4-byte aligned,
+/// and the whole register block must fit in the address space.
+#[allow(clippy::upper_case_acronyms)]
+#[cfg(feature = "pfic")]
+#[derive(Default)]
+pub struct PFIC<const BASE: usize> {
+    _marker: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "pfic")]
+impl<const BASE: usize> PFIC<BASE> {
+    /// Pointer to the register block
+    pub const PTR: *const self::pfic::RegisterBlock = {
+        assert!(BASE != 0, "PFIC base address must not be null");
+        assert!(BASE % 4 == 0, "PFIC base address must be 4-byte aligned");
+        assert!(
+            BASE.checked_add(core::mem::size_of::<self::pfic::RegisterBlock>())
+                .is_some(),
+            "PFIC register block must fit in the address space"
+        );
+        BASE as *const _
+    };
+
+    /// Creates a new interface for the PFIC peripheral. PACs can use this
+    /// function to add a PFIC interface to their `Peripherals` struct.
+    pub const fn new() -> Self {
+        // Force the compile-time validation of BASE
+        let _ = Self::PTR;
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Interface for the VexRiscv external interrupt controller.
+///
+/// # Note
+///
+/// This structure requires the `vexriscv` feature.
+///
+/// Unlike memory-mapped interrupt controllers, it is accessed through custom CSRs.
+/// Thus, it does not need a base address.
+#[cfg(feature = "vexriscv")]
+#[derive(Default)]
+pub struct VexRiscvIntc {
+    _marker: PhantomData<*const ()>,
+}
+
+#[cfg(feature = "vexriscv")]
+impl VexRiscvIntc {
+    /// Creates a new interface for the VexRiscv external interrupt controller. PACs can use this
+    /// function to add an interrupt controller interface to their `Peripherals` struct.
+    pub const fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
 /// Interface for a context of the PLIC peripheral.
 ///
 /// # Note
@@ -22,6 +200,16 @@ pub mod plic;
 /// You can use the [`crate::plic_context`] macro to generate a specific structure
 /// for interfacing every PLIC context of your platform. The resulting structure
 /// replaces generic types with the specific types of your target.
+///
+/// The base address is validated at compile time. It must be non-null, 4-byte aligned,
+/// and the whole register block must fit in the address space.
+/// The context number must be lower than the maximum number of contexts of the PLIC standard.
+///
+/// ``` compile_fail
+/// use riscv::peripheral::PLIC;
+///
+/// let plic = PLIC::<0x0C00_0001, 0>::new(); // misaligned base address
+/// ```
 #[allow(clippy::upper_case_acronyms)]
 #[cfg(feature = "plic")]
 #[derive(Default)]
@@ -32,13 +220,91 @@ pub struct PLIC<const BASE: usize, const CONTEXT: usize> {
 #[cfg(feature = "plic")]
 impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
     /// Pointer to the register block
-    pub const PTR: *const self::plic::RegisterBlock = BASE as *const _;
+    pub const PTR: *const self::plic::RegisterBlock = {
+        assert!(BASE != 0, "PLIC base address must not be null");
+        assert!(BASE % 4 == 0, "PLIC base address must be 4-byte aligned");
+        assert!(
+            BASE.checked_add(core::mem::size_of::<self::plic::RegisterBlock>())
+                .is_some(),
+            "PLIC register block must fit in the address space"
+        );
+        assert!(
+            CONTEXT < self::plic::MAX_CONTEXTS,
+            "PLIC context number out of range"
+        );
+        BASE as *const _
+    };
 
     /// Creates a new interface for the PLIC peripheral. PACs can use this
     /// function to add a PLIC interface to their `Peripherals` struct.
     pub const fn new() -> Self {
+        // Force the compile-time validation of BASE and CONTEXT
+        let _ = Self::PTR;
         Self {
             _marker: PhantomData,
         }
     }
 }
+
+/// Trait for enums of interrupt numbers.
+///
+/// This trait should be implemented by a peripheral access crate (PAC)
+/// on its enum of available external interrupts for a specific device.
+/// Each variant must convert to a `u16` of its interrupt number.
+///
+/// # Note
+///
+/// Recall that, in the PLIC, the interrupt number `0` is reserved as "no interrupt".
+///
+/// # Safety
+///
+/// This trait must only be implemented on enums of external interrupts. Each
+/// enum variant must represent a distinct value (no duplicates are permitted),
+/// and must always return the same value (do not change at runtime).
+/// All the interrupt numbers must be less than or equal to `MAX_INTERRUPT_NUMBER`.
+/// `MAX_INTERRUPT_NUMBER` must coincide with the highest allowed interrupt number.
+///
+/// These requirements ensure safe nesting of critical sections.
+pub unsafe trait InterruptNumber: Copy {
+    /// Highest number assigned to an interrupt source.
+    const MAX_INTERRUPT_NUMBER: u16;
+
+    /// Converts an interrupt source to its corresponding number.
+    fn number(self) -> u16;
+
+    /// Tries to convert a number to a valid interrupt source.
+    /// If the conversion fails, it returns an error with the number back.
+    fn try_from(value: u16) -> Result<Self, u16>;
+}
+
+/// Trait for enums of interrupt priority numbers.
+///
+/// This trait should be implemented by a peripheral access crate (PAC)
+/// on its enum of available priority numbers for a specific device.
+/// Each variant must convert to a `u8` of its priority level.
+///
+/// # Note
+///
+/// Recall that the priority number `0` is reserved as "never interrupt".
+///
+/// # Safety
+///
+/// This trait must only be implemented on enums of priority levels. Each
+/// enum variant must represent a distinct value (no duplicates are permitted),
+/// and must always return the same value (do not change at runtime).
+/// There must be a valid priority number set to 0 (i.e., never interrupt).
+/// All the priority level numbers must be less than or equal to `MAX_PRIORITY_NUMBER`.
+/// `MAX_PRIORITY_NUMBER` must coincide with the highest allowed priority number.
+///
+/// These requirements ensure safe nesting of critical sections.
+pub unsafe trait PriorityNumber: Copy {
+    /// Number assigned to the highest priority level.
+    const MAX_PRIORITY_NUMBER: u8;
+
+    /// Converts a priority level to its corresponding number.
+    fn number(self) -> u8;
+
+    /// Tries to convert a number to a valid priority level.
+    /// If the conversion fails, it returns an error with the number back.
+    fn try_from(value: u8) -> Result<Self, u8>;
+}
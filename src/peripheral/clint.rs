@@ -0,0 +1,425 @@
+//! Core-Local Interruptor (CLINT) peripheral.
+//!
+//! Specification: <https://github.com/riscv/riscv-aclint/blob/main/riscv-aclint.adoc>
+//!
+//! Some SoCs only allow 32-bit accesses to `mtime` and `mtimecmp`, and 64-bit accesses fault.
+//! With the `clint-split` feature, these registers are always accessed as two 32-bit halves,
+//! with the same sequencing used on RV32 targets. Drivers can also access the halves directly
+//! with [`RegisterBlock::mtime_split`] and [`RegisterBlock::mtimecmp_split`].
+
+use super::reg::Reg;
+pub mod future;
+pub use super::CLINT;
+use crate::interrupt::{self, WakeReason};
+use crate::register::{mhartid, mie};
+use crate::time::{Duration, Instant};
+
+/// Maximum number of HARTs supported by the CLINT.
+pub(super) const MAX_HARTS: usize = 4_095;
+
+/// Interrupt code of the machine timer interrupt.
+const MTIMER_CODE: usize = 7;
+
+/// Register block.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// `0x0000_0000..=0x0000_3FF8` - Machine Software Interrupt Pending Registers (one per HART).
+    pub msip: [Reg<u32>; MAX_HARTS],
+    /// `0x0000_3FFC` - Reserved.
+    _reserved: u32,
+    /// `0x0000_4000..=0x0000_BFF0` - Machine Timer Compare Registers (one per HART).
+    pub mtimecmp: [Reg<u64>; MAX_HARTS],
+    /// `0x0000_BFF8` - Machine Timer Register.
+    pub mtime: Reg<u64>,
+}
+
+/// 64-bit timer register accessed as two 32-bit halves.
+#[repr(C)]
+pub struct Split64 {
+    /// Lower 32 bits.
+    pub lo: Reg<u32>,
+    /// Upper 32 bits.
+    pub hi: Reg<u32>,
+}
+
+impl RegisterBlock {
+    /// Returns the `mtime` register as two 32-bit halves.
+    #[inline]
+    pub fn mtime_split(&self) -> &Split64 {
+        Split64::from_reg(&self.mtime)
+    }
+
+    /// Returns the `mtimecmp` register of a given HART as two 32-bit halves.
+    #[inline]
+    pub fn mtimecmp_split(&self, hart: usize) -> &Split64 {
+        Split64::from_reg(&self.mtimecmp[hart])
+    }
+}
+
+impl Split64 {
+    /// Returns a 64-bit register as two 32-bit halves.
+    #[inline]
+    fn from_reg(reg: &Reg<u64>) -> &Self {
+        // SAFETY: `Split64` has the same size and layout as a little-endian `Reg<u64>`
+        unsafe { &*(reg.as_ptr() as *const Self) }
+    }
+}
+
+/// Outcome of [`CLINT::wait_until`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum WaitResult {
+    /// The deadline passed before any other interrupt became pending.
+    TimedOut,
+    /// Another enabled interrupt became pending before the deadline.
+    /// It contains the interrupt code (i.e., its bit in `mip`).
+    Interrupted(usize),
+}
+
+impl<const BASE: usize> CLINT<BASE> {
+    /// Sets the Machine Timer Interrupt bit of the [`crate::register::mie`] CSR.
+    #[inline]
+    pub fn enable_mtimer() {
+        // SAFETY: atomic CSRRS instruction with no side effects
+        unsafe { mie::set_mtimer() };
+    }
+
+    /// Clears the Machine Timer Interrupt bit of the [`crate::register::mie`] CSR.
+    #[inline]
+    pub fn disable_mtimer() {
+        // SAFETY: atomic CSRRC instruction with no side effects
+        unsafe { mie::clear_mtimer() };
+    }
+
+    /// Sets the Machine Software Interrupt bit of the [`crate::register::mie`] CSR.
+    #[inline]
+    pub fn enable_msoft() {
+        // SAFETY: atomic CSRRS instruction with no side effects
+        unsafe { mie::set_msoft() };
+    }
+
+    /// Clears the Machine Software Interrupt bit of the [`crate::register::mie`] CSR.
+    #[inline]
+    pub fn disable_msoft() {
+        // SAFETY: atomic CSRRC instruction with no side effects
+        unsafe { mie::clear_msoft() };
+    }
+
+    /// Returns the current value of the `mtime` register.
+    ///
+    /// On RV32 targets (or with the `clint-split` feature), both halves are read until the upper half is stable.
+    #[inline]
+    pub fn mtime() -> u64 {
+        // SAFETY: atomic reads with no side effects
+        unsafe { read64(&(*Self::PTR).mtime) }
+    }
+
+    /// Returns the current value of the `mtime` register as an [`Instant`].
+    #[inline]
+    pub fn now() -> Instant {
+        Instant::from_ticks(Self::mtime())
+    }
+
+    /// Sets the value of the `mtime` register.
+    ///
+    /// # Note
+    ///
+    /// The `mtime` register is shared among all the HARTs.
+    #[inline]
+    pub fn set_mtime(&mut self, value: u64) {
+        // SAFETY: writes with no side effects. On RV32 (or with `clint-split`), the lower half is cleared first
+        // to prevent carries to the upper half while it is updated.
+        unsafe { write64(&self.mtime, 0, value) };
+    }
+
+    /// Returns the skew (in ticks) of the `mtime` register with respect to a reference timer.
+    ///
+    /// The reference is read right before and after `mtime`, and compared with their midpoint.
+    /// A positive skew means that `mtime` is ahead of the reference.
+    /// Both timers must run at the same frequency.
+    #[inline]
+    pub fn mtime_skew<F: FnMut() -> u64>(mut reference: F) -> i64 {
+        let before = reference();
+        let local = Self::mtime();
+        let after = reference();
+        let midpoint = before.wrapping_add(after.wrapping_sub(before) / 2);
+        local.wrapping_sub(midpoint) as i64
+    }
+
+    /// Synchronizes the `mtime` register with a reference timer (e.g., the timer of another
+    /// cluster, or an always-on timer after deep sleep). It returns the remaining skew (see [`Self::mtime_skew`]).
+    ///
+    /// It first measures how long a reference read plus an `mtime` write take, and then writes
+    /// `mtime` with the reference value advanced by that latency. On RV32 targets, [`Self::set_mtime`]
+    /// clears the lower half before updating the upper half, so no carry corrupts the new value.
+    ///
+    /// # Note
+    ///
+    /// The `mtime` register is shared among all the HARTs of the CLINT. Only one HART must synchronize it,
+    /// and timer interrupts of the other HARTs may fire early or late while it is updated.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use riscv::peripheral::CLINT;
+    ///
+    /// type Cluster0 = CLINT<0x0200_0000>;
+    /// type Cluster1 = CLINT<0x0300_0000>;
+    ///
+    /// let mut clint = Cluster1::new();
+    /// let skew = clint.sync_mtime(Cluster0::mtime);
+    /// ```
+    #[inline]
+    pub fn sync_mtime<F: FnMut() -> u64>(&mut self, mut reference: F) -> i64 {
+        let start = reference();
+        self.set_mtime(start);
+        let latency = reference().wrapping_sub(start);
+        let now = reference();
+        self.set_mtime(now.wrapping_add(latency));
+        Self::mtime_skew(reference)
+    }
+
+    /// Returns the value of the `mtimecmp` register of a given HART.
+    #[inline]
+    pub fn mtimecmp(hart: usize) -> u64 {
+        // SAFETY: atomic reads with no side effects
+        unsafe { read64(&(*Self::PTR).mtimecmp[hart]) }
+    }
+
+    /// Sets the value of the `mtimecmp` register of a given HART.
+    ///
+    /// On RV32 targets (or with the `clint-split` feature), the lower half is set to the maximum value first,
+    /// so no spurious timer interrupt is triggered while the upper half is updated.
+    #[inline]
+    pub fn set_mtimecmp(&mut self, hart: usize, value: u64) {
+        // SAFETY: writes with no side effects
+        unsafe { write64(&self.mtimecmp[hart], u32::MAX, value) };
+    }
+
+    /// Schedules a machine timer interrupt on a given HART at a given instant.
+    #[inline]
+    pub fn set_deadline(&mut self, hart: usize, deadline: Instant) {
+        self.set_mtimecmp(hart, deadline.ticks());
+    }
+
+    /// Schedules a machine timer interrupt on a given HART after a given duration.
+    /// It returns the instant at which the interrupt is scheduled.
+    #[inline]
+    pub fn set_timeout(&mut self, hart: usize, timeout: Duration) -> Instant {
+        let deadline = Self::now() + timeout;
+        self.set_deadline(hart, deadline);
+        deadline
+    }
+
+    /// Blocks the current HART until another interrupt becomes pending or the deadline passes.
+    ///
+    /// The `mtimecmp` register of the current HART is set to the deadline, the machine timer interrupt
+    /// is enabled, and the HART waits with `WFI` and interrupts globally disabled. Before returning,
+    /// the previous values of `mtimecmp` and of the Machine Timer Interrupt bit of `mie` are restored.
+    /// The interrupt that ended the wait (if any) is still pending, and its trap is taken once
+    /// interrupts are enabled again.
+    ///
+    /// # Note
+    ///
+    /// A deadline already armed on the current HART (e.g., a scheduler tick or a [`DeadlineGuard`])
+    /// is not canceled. If it is earlier than `deadline`, it is kept armed during the wait, and the wait
+    /// returns [`WaitResult::Interrupted`] with the machine timer interrupt code when it expires.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use riscv::peripheral::{clint::WaitResult, CLINT};
+    /// use riscv::time::Duration;
+    ///
+    /// let mut clint = CLINT::<0x0200_0000>::new();
+    /// let deadline = CLINT::<0x0200_0000>::now() + Duration::from_ticks(10_000);
+    /// match clint.wait_until(deadline) {
+    ///     WaitResult::TimedOut => { /* handle the timeout */ }
+    ///     WaitResult::Interrupted(code) => { /* check the device that raised `code` */ }
+    /// }
+    /// ```
+    #[inline]
+    pub fn wait_until(&mut self, deadline: Instant) -> WaitResult {
+        let hart = mhartid::read();
+        interrupt::free(|| {
+            let previous = Self::mtimecmp(hart);
+            let mtimer_enabled = mie::read().mtimer();
+            // the hardware compares `mtime` and `mtimecmp` as unsigned values
+            let own_deadline = deadline.ticks() < previous;
+            if own_deadline {
+                self.set_deadline(hart, deadline);
+            }
+            Self::enable_mtimer();
+            let result = loop {
+                if Self::now() >= deadline {
+                    break WaitResult::TimedOut;
+                }
+                // SAFETY: interrupts are masked, so `mcause` is not modified
+                match unsafe { interrupt::wfi_and_identify(true) } {
+                    WakeReason::Pending(MTIMER_CODE) if own_deadline => {}
+                    WakeReason::Pending(code) => break WaitResult::Interrupted(code),
+                    _ => {}
+                }
+            };
+            if own_deadline {
+                self.set_mtimecmp(hart, previous);
+            }
+            if !mtimer_enabled {
+                Self::disable_mtimer();
+            }
+            result
+        })
+    }
+
+    /// Arms a watchdog-style deadline on a given HART that expires after a given timeout.
+    ///
+    /// The machine timer interrupt fires unless the returned guard is refreshed (see [`DeadlineGuard::refresh`])
+    /// or dropped before the deadline. The machine timer interrupt must be enabled for the deadline to be reported.
+    ///
+    /// # Example
+    ///
+    /// ``` no_run
+    /// use riscv::peripheral::CLINT;
+    /// use riscv::time::Duration;
+    ///
+    /// let mut clint = CLINT::<0x0200_0000>::new();
+    /// let mut guard = clint.deadline_guard(0, Duration::from_ticks(100_000));
+    /// loop {
+    ///     // control loop iteration
+    ///     guard.refresh();
+    /// }
+    /// ```
+    #[inline]
+    pub fn deadline_guard(&mut self, hart: usize, timeout: Duration) -> DeadlineGuard<'_, BASE> {
+        let deadline = self.set_timeout(hart, timeout);
+        DeadlineGuard {
+            clint: self,
+            hart,
+            timeout,
+            deadline,
+        }
+    }
+
+    /// Checks if the machine software interrupt of a given HART is pending.
+    #[inline]
+    pub fn is_msip_pending(hart: usize) -> bool {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).msip[hart].read() & 1 == 1 }
+    }
+
+    /// Triggers a machine software interrupt on a given HART.
+    #[inline]
+    pub fn set_msip(&mut self, hart: usize) {
+        // SAFETY: atomic write with no side effects
+        unsafe { self.msip[hart].write(1) };
+    }
+
+    /// Clears the machine software interrupt of a given HART.
+    #[inline]
+    pub fn clear_msip(&mut self, hart: usize) {
+        // SAFETY: atomic write with no side effects
+        unsafe { self.msip[hart].write(0) };
+    }
+
+    /// Requests a context switch by pending the machine software interrupt of the current HART.
+    ///
+    /// This is the RISC-V counterpart of pending `PendSV` on Cortex-M. The handshake with the
+    /// machine software interrupt handler of the scheduler is as follows:
+    ///
+    /// 1. A task (or an interrupt handler) calls this function. If machine software interrupts are
+    ///    enabled and not masked, the trap is taken right away (or when the current handler returns).
+    /// 2. The handler clears the request with [`CLINT::clear_msip`] *before* switching contexts, so a
+    ///    new request during the switch is not lost.
+    /// 3. The handler saves the context of the current task, selects the next one,
+    ///    and restores its context before returning with `mret`.
+    ///
+    /// Controllers that pend software interrupts differently (e.g., CLIC-based controllers) follow
+    /// the same handshake with their own pend and unpend operations.
+    #[inline]
+    pub fn yield_to_scheduler(&mut self) {
+        self.set_msip(mhartid::read());
+    }
+}
+
+impl<const BASE: usize> core::ops::Deref for CLINT<BASE> {
+    type Target = RegisterBlock;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+
+unsafe impl<const BASE: usize> Send for CLINT<BASE> {}
+
+/// Watchdog-style deadline on the `mtimecmp` register of a HART (see [`CLINT::deadline_guard`]).
+///
+/// Dropping the guard disarms the deadline by setting `mtimecmp` to the maximum value.
+pub struct DeadlineGuard<'a, const BASE: usize> {
+    clint: &'a mut CLINT<BASE>,
+    hart: usize,
+    timeout: Duration,
+    deadline: Instant,
+}
+
+impl<'a, const BASE: usize> DeadlineGuard<'a, BASE> {
+    /// Returns the instant at which the machine timer interrupt fires if the guard is not refreshed.
+    #[inline]
+    pub fn deadline(&self) -> Instant {
+        self.deadline
+    }
+
+    /// Re-arms the deadline one timeout from now. It returns the new deadline.
+    #[inline]
+    pub fn refresh(&mut self) -> Instant {
+        self.deadline = self.clint.set_timeout(self.hart, self.timeout);
+        self.deadline
+    }
+}
+
+impl<'a, const BASE: usize> Drop for DeadlineGuard<'a, BASE> {
+    #[inline]
+    fn drop(&mut self) {
+        self.clint.set_mtimecmp(self.hart, u64::MAX);
+    }
+}
+
+/// Reads a 64-bit register.
+/// On RV32 targets (or with the `clint-split` feature), both halves are read until the upper half is stable.
+#[inline]
+unsafe fn read64(reg: &Reg<u64>) -> u64 {
+    match () {
+        #[cfg(any(target_pointer_width = "32", feature = "clint-split"))]
+        () => {
+            let split = Split64::from_reg(reg);
+            loop {
+                let hi = split.hi.read();
+                let lo = split.lo.read();
+                if hi == split.hi.read() {
+                    return ((hi as u64) << 32) | lo as u64;
+                }
+            }
+        }
+
+        #[cfg(not(any(target_pointer_width = "32", feature = "clint-split")))]
+        () => reg.read(),
+    }
+}
+
+/// Writes a 64-bit register.
+/// On RV32 targets (or with the `clint-split` feature), the lower half is set to `filler` before updating the upper half.
+#[inline]
+#[allow(unused_variables)]
+unsafe fn write64(reg: &Reg<u64>, filler: u32, value: u64) {
+    match () {
+        #[cfg(any(target_pointer_width = "32", feature = "clint-split"))]
+        () => {
+            let split = Split64::from_reg(reg);
+            split.lo.write(filler);
+            split.hi.write((value >> 32) as u32);
+            split.lo.write(value as u32);
+        }
+
+        #[cfg(not(any(target_pointer_width = "32", feature = "clint-split")))]
+        () => reg.write(value),
+    }
+}
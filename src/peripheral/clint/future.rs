@@ -0,0 +1,186 @@
+//! Futures for awaiting machine timer deadlines.
+//!
+//! A [`DelayFuture`] programs the `mtimecmp` register of the current HART when first polled
+//! and completes once `mtime` reaches its deadline. The machine timer interrupt handler wakes
+//! the awaiting task through [`CLINT::wake_timer`], so executors can sleep instead of polling `mtime`.
+//! Wakers are stored in a user-provided [`TimerWakers`] table, usually a `static`.
+//!
+//! Each HART has a single `mtimecmp` register, so at most one delay future can be pending
+//! on each HART at a time.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::peripheral::{clint::future::TimerWakers, CLINT};
+//! use riscv::time::Duration;
+//!
+//! type Clint = CLINT<0x0200_0000>;
+//! static WAKERS: TimerWakers<1> = TimerWakers::new();
+//!
+//! async fn blink() {
+//!     Clint::delay(&WAKERS, Duration::from_ticks(10_000)).await;
+//! }
+//!
+//! // Machine timer interrupt handler
+//! fn machine_timer() {
+//!     Clint::wake_timer(&WAKERS);
+//! }
+//! ```
+
+use super::{write64, CLINT};
+use crate::register::{mhartid, mie};
+use crate::time::{Duration, Instant};
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use critical_section::Mutex;
+
+const NO_WAKER: Option<Waker> = None;
+
+/// Table of timer wakers for the first `N` HARTs.
+pub struct TimerWakers<const N: usize> {
+    wakers: Mutex<RefCell<[Option<Waker>; N]>>,
+}
+
+impl<const N: usize> TimerWakers<N> {
+    /// Creates a new, empty waker table.
+    pub const fn new() -> Self {
+        Self {
+            wakers: Mutex::new(RefCell::new([NO_WAKER; N])),
+        }
+    }
+}
+
+impl<const N: usize> Default for TimerWakers<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future that completes when `mtime` reaches a deadline.
+///
+/// The deadline is disarmed (i.e., `mtimecmp` is set to the maximum value) when the future is dropped.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct DelayFuture<'a, const BASE: usize, const N: usize> {
+    wakers: &'a TimerWakers<N>,
+    hart: usize,
+    deadline: Instant,
+    armed: bool,
+}
+
+impl<const BASE: usize> CLINT<BASE> {
+    /// Returns a future that completes at a given instant on the current HART.
+    ///
+    /// The HART ID of the current HART must be lower than `N`.
+    #[inline]
+    pub fn delay_until<const N: usize>(
+        wakers: &TimerWakers<N>,
+        deadline: Instant,
+    ) -> DelayFuture<'_, BASE, N> {
+        let hart = mhartid::read();
+        assert!(hart < N, "no waker slot for HART");
+        DelayFuture {
+            wakers,
+            hart,
+            deadline,
+            armed: false,
+        }
+    }
+
+    /// Returns a future that completes after a given duration on the current HART.
+    ///
+    /// The HART ID of the current HART must be lower than `N`.
+    #[inline]
+    pub fn delay<const N: usize>(
+        wakers: &TimerWakers<N>,
+        duration: Duration,
+    ) -> DelayFuture<'_, BASE, N> {
+        Self::delay_until(wakers, Self::now() + duration)
+    }
+
+    /// Handler hook for futures created with [`Self::delay`] and [`Self::delay_until`].
+    ///
+    /// It disarms the deadline of the current HART, so the machine timer interrupt is no longer pending,
+    /// and wakes the awaiting task. Call it from the machine timer interrupt handler.
+    /// It returns `true` if a task was awaiting the deadline.
+    #[inline]
+    pub fn wake_timer<const N: usize>(wakers: &TimerWakers<N>) -> bool {
+        let hart = mhartid::read();
+        if hart >= N {
+            return false;
+        }
+        critical_section::with(|cs| {
+            // SAFETY: the deadline is disarmed within a critical section
+            unsafe { Self::disarm(hart) };
+            wakers.wakers.borrow(cs).borrow_mut()[hart].take()
+        })
+        .map(Waker::wake)
+        .is_some()
+    }
+
+    /// Sets the `mtimecmp` register of a given HART without requiring a CLINT instance.
+    ///
+    /// # Safety
+    ///
+    /// Must be called within a critical section.
+    #[inline]
+    unsafe fn arm(hart: usize, deadline: Instant) {
+        write64(&(*Self::PTR).mtimecmp[hart], u32::MAX, deadline.ticks());
+    }
+
+    /// Sets the `mtimecmp` register of a given HART to the maximum value without requiring a CLINT instance.
+    ///
+    /// # Safety
+    ///
+    /// Must be called within a critical section.
+    #[inline]
+    unsafe fn disarm(hart: usize) {
+        write64(&(*Self::PTR).mtimecmp[hart], u32::MAX, u64::MAX);
+    }
+}
+
+// The future holds no self-references
+impl<const BASE: usize, const N: usize> Unpin for DelayFuture<'_, BASE, N> {}
+
+impl<const BASE: usize, const N: usize> Future for DelayFuture<'_, BASE, N> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        critical_section::with(|cs| {
+            let mut wakers = this.wakers.wakers.borrow(cs).borrow_mut();
+            if CLINT::<BASE>::now() >= this.deadline {
+                if this.armed {
+                    wakers[this.hart] = None;
+                    this.armed = false;
+                    // SAFETY: the deadline is disarmed within a critical section
+                    unsafe { CLINT::<BASE>::disarm(this.hart) };
+                }
+                return Poll::Ready(());
+            }
+            wakers[this.hart] = Some(cx.waker().clone());
+            // The deadline is armed on every poll, as the handler hook disarms it
+            this.armed = true;
+            // SAFETY: the deadline is armed within a critical section, and enabling
+            // the machine timer interrupt has no side effects
+            unsafe {
+                CLINT::<BASE>::arm(this.hart, this.deadline);
+                mie::set_mtimer();
+            }
+            Poll::Pending
+        })
+    }
+}
+
+impl<const BASE: usize, const N: usize> Drop for DelayFuture<'_, BASE, N> {
+    fn drop(&mut self) {
+        if self.armed {
+            critical_section::with(|cs| {
+                // SAFETY: the deadline is disarmed within a critical section
+                unsafe { CLINT::<BASE>::disarm(self.hart) };
+                self.wakers.wakers.borrow(cs).borrow_mut()[self.hart] = None;
+            });
+        }
+    }
+}
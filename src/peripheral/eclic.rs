@@ -0,0 +1,525 @@
+//! Nuclei Enhanced Core-Local Interrupt Controller (ECLIC) peripheral.
+//!
+//! Specification: <https://doc.nucleisys.com/nuclei_spec/isa/eclic.html>
+//!
+//! The ECLIC is based on the RISC-V CLIC draft, but it has its own register layout.
+//! The `clicintctl` register of every interrupt source is split into a level field
+//! (the upper `nlbits` bits) and a priority field (the remaining implemented bits).
+//! Interrupts are preempted according to their level, while the priority only
+//! arbitrates pending interrupts of the same level.
+
+use super::reg::{Reg, RO};
+pub use super::{InterruptNumber, ECLIC};
+pub mod mutex;
+pub mod resource;
+use core::ops::Deref;
+
+/// Maximum number of interrupt sources supported by the ECLIC.
+const MAX_SOURCES: usize = 4_096;
+
+/// Register block.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// `0x0000_0000` - Configuration Register.
+    pub cliccfg: Reg<u8>,
+    /// `0x0000_0001..=0x0000_0003` - Reserved.
+    _reserved1: [u8; 3],
+    /// `0x0000_0004` - Information Register.
+    pub clicinfo: Reg<u32, RO>,
+    /// `0x0000_0008..=0x0000_000A` - Reserved.
+    _reserved2: [u8; 3],
+    /// `0x0000_000B` - Machine Mode Interrupt Level Threshold Register.
+    pub mth: Reg<u8>,
+    /// `0x0000_000C..=0x0000_0FFF` - Reserved.
+    _reserved3: [u8; 0x0ff4],
+    /// `0x0000_1000..=0x0000_4FFF` - Interrupt Registers (one per source).
+    pub interrupts: [InterruptRegisters; MAX_SOURCES],
+}
+
+/// Registers of a single interrupt source.
+#[repr(C)]
+pub struct InterruptRegisters {
+    /// `0x0000_0000` - Interrupt Pending Register.
+    pub clicintip: Reg<u8>,
+    /// `0x0000_0001` - Interrupt Enable Register.
+    pub clicintie: Reg<u8>,
+    /// `0x0000_0002` - Interrupt Attribute Register.
+    pub clicintattr: Reg<u8>,
+    /// `0x0000_0003` - Interrupt Level and Priority Control Register.
+    pub clicintctl: Reg<u8>,
+}
+
+/// Trigger type of an interrupt source.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Trigger {
+    /// Level-triggered interrupt
+    Level = 0,
+    /// Rising edge-triggered interrupt
+    PositiveEdge = 1,
+    /// Falling edge-triggered interrupt
+    NegativeEdge = 3,
+}
+
+/// Privilege mode an interrupt source is routed to.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Mode {
+    /// User mode
+    User = 0b00,
+    /// Supervisor mode
+    Supervisor = 0b01,
+    /// Machine mode
+    Machine = 0b11,
+}
+
+/// Decoded contents of the `clicintattr` register of an interrupt source.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct IntAttr {
+    /// Selective hardware vectoring
+    pub shv: bool,
+    /// Trigger type (level or edge, and polarity)
+    pub trigger: Trigger,
+    /// Privilege mode of the interrupt (the reserved value `0b10` is decoded as machine mode)
+    pub mode: Mode,
+}
+
+impl IntAttr {
+    /// Decodes the raw contents of a `clicintattr` register.
+    #[inline]
+    pub const fn from_bits(attr: u8) -> Self {
+        Self {
+            shv: attr & 1 == 1,
+            trigger: match (attr >> 1) & 0b11 {
+                0b01 => Trigger::PositiveEdge,
+                0b11 => Trigger::NegativeEdge,
+                _ => Trigger::Level,
+            },
+            mode: match attr >> 6 {
+                0b00 => Mode::User,
+                0b01 => Mode::Supervisor,
+                _ => Mode::Machine,
+            },
+        }
+    }
+}
+
+impl<const BASE: usize> ECLIC<BASE> {
+    /// Returns the number of interrupt sources supported by the ECLIC.
+    #[inline]
+    pub fn num_interrupts() -> u16 {
+        // SAFETY: atomic read with no side effects
+        (unsafe { (*Self::PTR).clicinfo.read() } & 0x1fff) as u16
+    }
+
+    /// Returns the hardware version of the ECLIC.
+    #[inline]
+    pub fn version() -> u8 {
+        // SAFETY: atomic read with no side effects
+        (unsafe { (*Self::PTR).clicinfo.read() } >> 13) as u8
+    }
+
+    /// Returns the number of implemented bits of the `clicintctl` registers.
+    #[inline]
+    pub fn ctl_bits() -> u8 {
+        // SAFETY: atomic read with no side effects
+        ((unsafe { (*Self::PTR).clicinfo.read() } >> 21) & 0xf) as u8
+    }
+
+    /// Returns the number of bits of `clicintctl` that encode the interrupt level.
+    #[inline]
+    pub fn nlbits() -> u8 {
+        // SAFETY: atomic read with no side effects
+        (unsafe { (*Self::PTR).cliccfg.read() } >> 1) & 0xf
+    }
+
+    /// Sets the number of bits of `clicintctl` that encode the interrupt level.
+    ///
+    /// # Safety
+    ///
+    /// Changing the level/priority split changes the level of all the interrupt sources.
+    /// This can break level-based critical sections and compromise memory safety.
+    #[inline]
+    pub unsafe fn set_nlbits(&mut self, nlbits: u8) {
+        self.cliccfg
+            .modify(|cfg| (cfg & !(0xf << 1)) | ((nlbits.min(8) & 0xf) << 1));
+    }
+
+    /// Returns the number of bits of `clicintattr` that encode the privilege mode of the interrupt.
+    #[inline]
+    pub fn nmbits() -> u8 {
+        // SAFETY: atomic read with no side effects
+        (unsafe { (*Self::PTR).cliccfg.read() } >> 5) & 0b11
+    }
+
+    /// Sets the number of bits of `clicintattr` that encode the privilege mode of the interrupt.
+    ///
+    /// # Safety
+    ///
+    /// Changing the number of mode bits changes the privilege mode of all the interrupt sources.
+    #[inline]
+    pub unsafe fn set_nmbits(&mut self, nmbits: u8) {
+        self.cliccfg
+            .modify(|cfg| (cfg & !(0b11 << 5)) | ((nmbits.min(2) & 0b11) << 5));
+    }
+
+    /// Returns the interrupt level threshold of machine mode.
+    #[inline]
+    pub fn threshold() -> u8 {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).mth.read() }
+    }
+
+    /// Sets the interrupt level threshold of machine mode.
+    /// Only interrupts with a level greater than the threshold can be taken.
+    ///
+    /// # Safety
+    ///
+    /// Lowering the threshold can break level-based critical sections.
+    #[inline]
+    pub unsafe fn set_threshold(&mut self, threshold: u8) {
+        self.mth.write(threshold);
+    }
+
+    /// Returns the threshold that masks all the interrupts with a level lower than or equal to `level`.
+    /// Levels greater than the maximum level supported by the ECLIC are clamped.
+    ///
+    /// Interrupt levels are compared with the threshold as 8-bit values, with the level
+    /// in the upper `nlbits` bits and the remaining bits set to 1.
+    #[inline]
+    pub fn level_threshold(level: u8) -> u8 {
+        let (nlbits, _) = Self::split();
+        let level = level.min(Self::field_max(nlbits)) as u32;
+        Self::fill_unimplemented(level << (8 - nlbits), nlbits)
+    }
+
+    /// Checks if an interrupt source is enabled.
+    #[inline]
+    pub fn is_interrupt_enabled<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).interrupts[source].clicintie.is_set(1) }
+    }
+
+    /// Enables an interrupt source.
+    ///
+    /// # Safety
+    ///
+    /// Enabling an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn enable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        self.interrupts[source].clicintie.write(1);
+    }
+
+    /// Disables an interrupt source.
+    #[inline]
+    pub fn disable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        // SAFETY: atomic write with no side effects
+        unsafe { self.interrupts[source].clicintie.write(0) };
+    }
+
+    /// Checks if an interrupt triggered by a given source is pending.
+    #[inline]
+    pub fn is_interrupt_pending<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).interrupts[source].clicintip.is_set(1) }
+    }
+
+    /// Sets the pending flag of an interrupt source.
+    ///
+    /// # Note
+    ///
+    /// The pending flag of level-triggered interrupt sources follows the source signal,
+    /// so writing to it has no effect.
+    #[inline]
+    pub fn pend<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        // SAFETY: atomic write with no side effects
+        unsafe { self.interrupts[source].clicintip.write(1) };
+    }
+
+    /// Clears the pending flag of an interrupt source.
+    ///
+    /// # Note
+    ///
+    /// The pending flag of level-triggered interrupt sources follows the source signal,
+    /// so writing to it has no effect.
+    #[inline]
+    pub fn unpend<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        // SAFETY: atomic write with no side effects
+        unsafe { self.interrupts[source].clicintip.write(0) };
+    }
+
+    /// Returns the decoded attributes of an interrupt source.
+    #[inline]
+    pub fn attribute<I: InterruptNumber>(source: I) -> IntAttr {
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        IntAttr::from_bits(unsafe { (*Self::PTR).interrupts[source].clicintattr.read() })
+    }
+
+    /// Returns the trigger type of an interrupt source.
+    #[inline]
+    pub fn trigger<I: InterruptNumber>(source: I) -> Trigger {
+        Self::attribute(source).trigger
+    }
+
+    /// Sets the trigger type of an interrupt source.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline]
+    pub unsafe fn set_trigger<I: InterruptNumber>(&mut self, source: I, trigger: Trigger) {
+        let source = usize::from(source.number());
+        self.interrupts[source]
+            .clicintattr
+            .modify(|attr| (attr & !(0b11 << 1)) | ((trigger as u8) << 1));
+    }
+
+    /// Returns the privilege mode an interrupt source is routed to.
+    #[inline]
+    pub fn mode<I: InterruptNumber>(source: I) -> Mode {
+        match Self::nmbits() {
+            0 => Mode::Machine,
+            _ => Self::attribute(source).mode,
+        }
+    }
+
+    /// Routes an interrupt source to a given privilege mode.
+    ///
+    /// With `nmbits == 1`, only the most significant mode bit is used. Thus, `Supervisor`
+    /// and `User` both route the interrupt to the less privileged mode of the platform.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `nmbits == 0` and `mode` is not `Machine`, as all the interrupts are
+    /// handled in machine mode.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    /// Additionally, the interrupt must be handled by the target privilege mode.
+    #[inline]
+    pub unsafe fn set_mode<I: InterruptNumber>(&mut self, source: I, mode: Mode) {
+        assert!(
+            mode == Mode::Machine || Self::nmbits() > 0,
+            "interrupts cannot be delegated with nmbits == 0"
+        );
+        let source = usize::from(source.number());
+        self.interrupts[source]
+            .clicintattr
+            .modify(|attr| (attr & !(0b11 << 6)) | ((mode as u8) << 6));
+    }
+
+    /// Checks if an interrupt source uses hardware vectoring.
+    #[inline]
+    pub fn is_vectored<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).interrupts[source].clicintattr.is_set(1) }
+    }
+
+    /// Enables or disables hardware vectoring for an interrupt source.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    /// Additionally, a vectored interrupt source must have a valid entry in the vector table.
+    #[inline]
+    pub unsafe fn set_vectored<I: InterruptNumber>(&mut self, source: I, vectored: bool) {
+        let source = usize::from(source.number());
+        match vectored {
+            true => self.interrupts[source].clicintattr.set_bits(1),
+            false => self.interrupts[source].clicintattr.clear_bits(1),
+        }
+    }
+
+    /// Returns the level of an interrupt source.
+    /// The level is a number in the range `0..2^nlbits`.
+    #[inline]
+    pub fn level<I: InterruptNumber>(source: I) -> u8 {
+        let (nlbits, _) = Self::split();
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        let ctl = unsafe { (*Self::PTR).interrupts[source].clicintctl.read() } as u32;
+        (ctl >> (8 - nlbits)) as u8 & Self::field_max(nlbits)
+    }
+
+    /// Sets the level of an interrupt source.
+    /// Levels greater than the maximum level supported by the ECLIC are clamped.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    /// Additionally, changing levels can break level-based critical sections.
+    #[inline]
+    pub unsafe fn set_level<I: InterruptNumber>(&mut self, source: I, level: u8) {
+        let (nlbits, ctlbits) = Self::split();
+        let level = level.min(Self::field_max(nlbits)) as u32;
+        let mask = (Self::field_max(nlbits) as u32) << (8 - nlbits);
+        let source = usize::from(source.number());
+        self.interrupts[source].clicintctl.modify(|ctl| {
+            let ctl = (ctl as u32 & !mask) | (level << (8 - nlbits));
+            Self::fill_unimplemented(ctl, ctlbits)
+        });
+    }
+
+    /// Returns the priority of an interrupt source.
+    /// The priority is a number in the range `0..2^(ctl_bits - nlbits)`.
+    #[inline]
+    pub fn priority<I: InterruptNumber>(source: I) -> u8 {
+        let (nlbits, ctlbits) = Self::split();
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        let ctl = unsafe { (*Self::PTR).interrupts[source].clicintctl.read() } as u32;
+        (ctl >> (8 - ctlbits)) as u8 & Self::field_max(ctlbits - nlbits)
+    }
+
+    /// Sets the priority of an interrupt source.
+    /// Priorities greater than the maximum priority supported by the ECLIC are clamped.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline]
+    pub unsafe fn set_priority<I: InterruptNumber>(&mut self, source: I, priority: u8) {
+        let (nlbits, ctlbits) = Self::split();
+        let max = Self::field_max(ctlbits - nlbits);
+        let priority = priority.min(max) as u32;
+        let mask = (max as u32) << (8 - ctlbits);
+        let source = usize::from(source.number());
+        self.interrupts[source].clicintctl.modify(|ctl| {
+            let ctl = (ctl as u32 & !mask) | (priority << (8 - ctlbits));
+            Self::fill_unimplemented(ctl, ctlbits)
+        });
+    }
+
+    /// Resets the ECLIC peripheral. Namely, it performs the following operations:
+    ///
+    /// - Sets the level threshold to the maximum level (i.e., never interrupt).
+    /// - Disables all the interrupt sources and clears their pending flags.
+    /// - Sets all the interrupt sources as level-triggered and non-vectored.
+    /// - Sets the level and priority of all the interrupt sources to 0.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline(always)]
+    pub unsafe fn reset<I: InterruptNumber>(&mut self) {
+        self.set_threshold(u8::MAX);
+        for source in (0..=I::MAX_INTERRUPT_NUMBER).filter_map(|n| I::try_from(n).ok()) {
+            self.disable_interrupt(source);
+            self.unpend(source);
+            self.interrupts[usize::from(source.number())]
+                .clicintattr
+                .write(0);
+            self.set_level(source, 0);
+            self.set_priority(source, 0);
+        }
+    }
+
+    /// Returns the number of level bits and implemented bits of the `clicintctl` registers.
+    #[inline]
+    fn split() -> (u32, u32) {
+        let ctlbits = Self::ctl_bits().min(8) as u32;
+        (Self::nlbits().min(ctlbits as u8) as u32, ctlbits)
+    }
+
+    /// Returns the maximum value of a field of `bits` bits.
+    #[inline]
+    fn field_max(bits: u32) -> u8 {
+        ((1u32 << bits) - 1) as u8
+    }
+
+    /// Sets the unimplemented bits of a `clicintctl` value, which are hardwired to 1.
+    #[inline]
+    fn fill_unimplemented(ctl: u32, ctlbits: u32) -> u8 {
+        (ctl | ((1 << (8 - ctlbits)) - 1)) as u8
+    }
+}
+
+/// Validates a level assignment at compile time.
+///
+/// - `levels` contains the interrupt number and the level of every interrupt source.
+/// - `nlbits` is the number of level bits that the application configures.
+/// - `preempts` contains pairs `(high, low)` of interrupt numbers, meaning that
+///   the interrupt `high` must have a greater level than `low` (i.e., `high` preempts `low`).
+///
+/// Use it in a constant context to turn an invalid assignment into a compilation error.
+/// PAC interrupt enums can be converted to interrupt numbers with `as u16`.
+///
+/// # Panics
+///
+/// Panics if `nlbits` is greater than 8, a level does not fit in `nlbits` bits,
+/// an interrupt source is assigned more than once, or a preemption constraint
+/// refers to an unassigned interrupt source or is not fulfilled.
+///
+/// # Example
+///
+/// ```
+/// use riscv::peripheral::eclic::validate_levels;
+///
+/// const UART0: u16 = 19;
+/// const TIMER0: u16 = 20;
+///
+/// const LEVELS: &[(u16, u8)] = &[(UART0, 1), (TIMER0, 2)];
+/// const _: () = validate_levels(LEVELS, 2, &[(TIMER0, UART0)]);
+/// ```
+///
+/// ``` compile_fail
+/// use riscv::peripheral::eclic::validate_levels;
+///
+/// // with 2 level bits, levels must be lower than 4
+/// const _: () = validate_levels(&[(19, 4)], 2, &[]);
+/// ```
+pub const fn validate_levels(levels: &[(u16, u8)], nlbits: u8, preempts: &[(u16, u16)]) {
+    assert!(nlbits <= 8, "nlbits must not be greater than 8");
+    let max = ((1u16 << nlbits) - 1) as u8;
+    let mut i = 0;
+    while i < levels.len() {
+        assert!(levels[i].1 <= max, "level does not fit in nlbits");
+        let mut j = i + 1;
+        while j < levels.len() {
+            assert!(
+                levels[i].0 != levels[j].0,
+                "interrupt source assigned more than once"
+            );
+            j += 1;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < preempts.len() {
+        let (high, low) = preempts[i];
+        let high = find_level(levels, high);
+        let low = find_level(levels, low);
+        assert!(high > low, "preemption constraint not fulfilled");
+        i += 1;
+    }
+}
+
+/// Returns the level of an interrupt source in a level assignment.
+const fn find_level(levels: &[(u16, u8)], source: u16) -> u8 {
+    let mut i = 0;
+    while i < levels.len() {
+        if levels[i].0 == source {
+            return levels[i].1;
+        }
+        i += 1;
+    }
+    panic!("preemption constraint refers to an unassigned interrupt source")
+}
+
+impl<const BASE: usize> Deref for ECLIC<BASE> {
+    type Target = RegisterBlock;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+
+unsafe impl<const BASE: usize> Send for ECLIC<BASE> {}
@@ -0,0 +1,101 @@
+//! Level-based mutual exclusion for the ECLIC.
+//!
+//! A [`LevelMutex`] protects data shared among contexts with an interrupt level lower than
+//! or equal to its *ceiling*. Locking the mutex raises the level threshold (`mth`) to the ceiling,
+//! so interrupts that may access the data are masked, while interrupts with higher levels
+//! are still taken. Unlike [`critical_section`], it does not disable interrupts globally.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::peripheral::eclic::mutex::LevelMutex;
+//!
+//! const ECLIC_BASE: usize = 0xD200_0000;
+//!
+//! // SAFETY: the counter is only accessed from thread mode and interrupts with level 2 or lower
+//! static COUNTER: LevelMutex<u32, ECLIC_BASE> = unsafe { LevelMutex::new(2, 0) };
+//!
+//! fn uart_handler() {
+//!     COUNTER.lock(|counter| *counter += 1);
+//! }
+//! ```
+
+use super::ECLIC;
+use core::cell::UnsafeCell;
+
+/// Mutex that masks interrupts up to a ceiling level while it is locked.
+pub struct LevelMutex<T, const BASE: usize> {
+    ceiling: u8,
+    locked: UnsafeCell<bool>,
+    value: UnsafeCell<T>,
+}
+
+impl<T, const BASE: usize> LevelMutex<T, BASE> {
+    /// Creates a new mutex with a given ceiling level.
+    ///
+    /// # Safety
+    ///
+    /// All the contexts that access the mutex must run with a level lower than or equal to `ceiling`.
+    /// Otherwise, they could preempt a context that holds the lock.
+    #[inline]
+    pub const unsafe fn new(ceiling: u8, value: T) -> Self {
+        Self {
+            ceiling,
+            locked: UnsafeCell::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns the ceiling level of the mutex.
+    #[inline]
+    pub const fn ceiling(&self) -> u8 {
+        self.ceiling
+    }
+
+    /// Locks the mutex and calls `f` with a mutable reference to the protected data.
+    ///
+    /// The level threshold is raised to the ceiling of the mutex (if it is not higher already)
+    /// while `f` runs, and restored afterwards.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the mutex is already locked (i.e., `lock` is called within `f`).
+    #[inline]
+    pub fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let threshold = ECLIC::<BASE>::threshold();
+        let ceiling = ECLIC::<BASE>::level_threshold(self.ceiling);
+        if ceiling > threshold {
+            // SAFETY: raising the threshold only masks interrupts
+            unsafe { (*ECLIC::<BASE>::PTR).mth.write(ceiling) };
+        }
+        // SAFETY: no other context that accesses the mutex can run while the threshold is raised
+        let locked = unsafe { &mut *self.locked.get() };
+        assert!(!*locked, "LevelMutex already locked");
+        *locked = true;
+        // SAFETY: `locked` is not used anymore, so `f` cannot alias it when it calls `lock` again
+        let result = f(unsafe { &mut *self.value.get() });
+        // SAFETY: the threshold is still raised
+        unsafe { *self.locked.get() = false };
+        if ceiling > threshold {
+            // SAFETY: the previous threshold is restored
+            unsafe { (*ECLIC::<BASE>::PTR).mth.write(threshold) };
+        }
+        result
+    }
+
+    /// Returns a mutable reference to the protected data.
+    /// No locking is needed, as the mutable borrow guarantees exclusive access.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    /// Consumes the mutex and returns the protected data.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+// SAFETY: accesses to the data are serialized by the level threshold
+unsafe impl<T: Send, const BASE: usize> Sync for LevelMutex<T, BASE> {}
@@ -0,0 +1,140 @@
+//! Stack Resource Policy (SRP) primitives for the ECLIC.
+//!
+//! These primitives follow the approach of the Real-Time Interrupt-driven Concurrency (RTIC) framework:
+//!
+//! - Every task (i.e., interrupt handler or thread mode) owns a [`Priority`] token with its
+//!   current dynamic level. It starts with the static level of the task.
+//! - Every [`Resource`] has a static *ceiling*: the highest level of the tasks that access it.
+//! - Locking a resource raises the level threshold (`mth`) to the ceiling only if the
+//!   dynamic level of the task is lower than the ceiling. Otherwise, no task that accesses the
+//!   resource can preempt the current one, and the lock does not touch the hardware.
+//!
+//! Unlike [`LevelMutex`](super::mutex::LevelMutex), the ceiling is a const generic parameter,
+//! and nested locks of resources with lower ceilings are free.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::peripheral::eclic::resource::{Priority, Resource};
+//!
+//! const ECLIC_BASE: usize = 0xD200_0000;
+//!
+//! // SAFETY: SHARED is accessed by tasks with levels 1 and 2
+//! static SHARED: Resource<u32, ECLIC_BASE, 2> = unsafe { Resource::new(0) };
+//!
+//! // Interrupt handler with level 1
+//! fn task1() {
+//!     // SAFETY: the static level of this task is 1
+//!     let priority = unsafe { Priority::new(1) };
+//!     SHARED.lock(&priority, |shared| *shared += 1);
+//! }
+//! ```
+
+use super::ECLIC;
+use core::cell::{Cell, UnsafeCell};
+
+/// Dynamic level of the running task.
+///
+/// It is not `Sync`, so it cannot be shared with other tasks.
+pub struct Priority {
+    level: Cell<u8>,
+}
+
+impl Priority {
+    /// Creates the priority token of a task.
+    ///
+    /// # Safety
+    ///
+    /// `level` must be the static level of the running task (0 for thread mode).
+    /// Only one token may exist per task.
+    #[inline]
+    pub const unsafe fn new(level: u8) -> Self {
+        Self {
+            level: Cell::new(level),
+        }
+    }
+
+    /// Returns the current dynamic level of the task.
+    #[inline]
+    pub fn level(&self) -> u8 {
+        self.level.get()
+    }
+}
+
+/// Resource shared among tasks with levels lower than or equal to `CEILING`.
+pub struct Resource<T, const BASE: usize, const CEILING: u8> {
+    locked: UnsafeCell<bool>,
+    value: UnsafeCell<T>,
+}
+
+impl<T, const BASE: usize, const CEILING: u8> Resource<T, BASE, CEILING> {
+    /// Creates a new resource.
+    ///
+    /// # Safety
+    ///
+    /// All the tasks that access the resource must have a static level lower than or equal to `CEILING`.
+    #[inline]
+    pub const unsafe fn new(value: T) -> Self {
+        Self {
+            locked: UnsafeCell::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns the ceiling level of the resource.
+    #[inline]
+    pub const fn ceiling(&self) -> u8 {
+        CEILING
+    }
+
+    /// Locks the resource and calls `f` with a mutable reference to the protected data.
+    ///
+    /// If the dynamic level of the task is lower than the ceiling, the level threshold is raised
+    /// to the ceiling (if it is not higher already) while `f` runs. Otherwise, `f` runs right away.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the resource is already locked (i.e., `lock` is called within `f`).
+    #[inline]
+    pub fn lock<R>(&self, priority: &Priority, f: impl FnOnce(&mut T) -> R) -> R {
+        let current = priority.level();
+        if current < CEILING {
+            let threshold = ECLIC::<BASE>::threshold();
+            let ceiling = ECLIC::<BASE>::level_threshold(CEILING);
+            if ceiling > threshold {
+                // SAFETY: raising the threshold only masks interrupts
+                unsafe { (*ECLIC::<BASE>::PTR).mth.write(ceiling) };
+            }
+            priority.level.set(CEILING);
+            // SAFETY: tasks that access the resource are masked
+            let result = unsafe { self.access(f) };
+            priority.level.set(current);
+            if ceiling > threshold {
+                // SAFETY: the previous threshold is restored
+                unsafe { (*ECLIC::<BASE>::PTR).mth.write(threshold) };
+            }
+            result
+        } else {
+            // SAFETY: tasks that access the resource cannot preempt the current one
+            unsafe { self.access(f) }
+        }
+    }
+
+    /// Calls `f` with a mutable reference to the protected data, marking the resource as locked.
+    ///
+    /// # Safety
+    ///
+    /// No other task that accesses the resource may run until this function returns.
+    #[inline]
+    unsafe fn access<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let locked = &mut *self.locked.get();
+        assert!(!*locked, "Resource already locked");
+        *locked = true;
+        let result = f(&mut *self.value.get());
+        *self.locked.get() = false;
+        result
+    }
+}
+
+// SAFETY: accesses to the data are serialized by the Stack Resource Policy
+unsafe impl<T: Send, const BASE: usize, const CEILING: u8> Sync for Resource<T, BASE, CEILING> {}
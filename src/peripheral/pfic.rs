@@ -0,0 +1,277 @@
+//! WCH QingKe Programmable Fast Interrupt Controller (PFIC) peripheral.
+//!
+//! Specification: QingKe V4 Microprocessor Manual, section 3.
+//!
+//! The PFIC manages up to 256 interrupt sources with a priority each.
+//! Up to 4 interrupt sources can use Vector Table Free (VTF) interrupts,
+//! which jump directly to a handler address without reading the vector table.
+
+use super::reg::{Reg, RO, WO};
+pub use super::{InterruptNumber, PFIC};
+use core::ops::Deref;
+
+/// Maximum number of interrupt sources supported by the PFIC.
+const MAX_SOURCES: usize = 256;
+/// Maximum number of words needed to represent interrupts with flags.
+const MAX_FLAGS_WORDS: usize = MAX_SOURCES / (u32::BITS as usize);
+/// Number of Vector Table Free (VTF) interrupt channels.
+pub const VTF_CHANNELS: usize = 4;
+
+/// Key for system reset requests in the `cfgr` register.
+const KEY3: u32 = 0xbeef;
+/// System reset bit of the `cfgr` register.
+const RESETSYS: u32 = 1 << 7;
+
+/// Register block.
+#[repr(C)]
+pub struct RegisterBlock {
+    /// `0x0000_0000..=0x0000_001C` - Interrupt Enable Status Registers.
+    pub isr: [Reg<u32, RO>; MAX_FLAGS_WORDS],
+    /// `0x0000_0020..=0x0000_003C` - Interrupt Pending Status Registers.
+    pub ipr: [Reg<u32, RO>; MAX_FLAGS_WORDS],
+    /// `0x0000_0040` - Interrupt Priority Threshold Register.
+    pub ithresdr: Reg<u32>,
+    /// `0x0000_0044` - Reserved.
+    _reserved1: u32,
+    /// `0x0000_0048` - Interrupt Configuration Register.
+    pub cfgr: Reg<u32>,
+    /// `0x0000_004C` - Global Interrupt Status Register.
+    pub gisr: Reg<u32, RO>,
+    /// `0x0000_0050..=0x0000_0053` - VTF Interrupt ID Registers (one per channel).
+    pub vtfidr: [Reg<u8>; VTF_CHANNELS],
+    /// `0x0000_0054..=0x0000_005F` - Reserved.
+    _reserved2: [u32; 3],
+    /// `0x0000_0060..=0x0000_006C` - VTF Interrupt Address Registers (one per channel).
+    pub vtfaddrr: [Reg<u32>; VTF_CHANNELS],
+    /// `0x0000_0070..=0x0000_00FF` - Reserved.
+    _reserved3: [u32; 36],
+    /// `0x0000_0100..=0x0000_011C` - Interrupt Enable Set Registers.
+    pub ienr: [Reg<u32, WO>; MAX_FLAGS_WORDS],
+    /// `0x0000_0120..=0x0000_017F` - Reserved.
+    _reserved4: [u32; 24],
+    /// `0x0000_0180..=0x0000_019C` - Interrupt Enable Clear Registers.
+    pub irer: [Reg<u32, WO>; MAX_FLAGS_WORDS],
+    /// `0x0000_01A0..=0x0000_01FF` - Reserved.
+    _reserved5: [u32; 24],
+    /// `0x0000_0200..=0x0000_021C` - Interrupt Pending Set Registers.
+    pub ipsr: [Reg<u32, WO>; MAX_FLAGS_WORDS],
+    /// `0x0000_0220..=0x0000_027F` - Reserved.
+    _reserved6: [u32; 24],
+    /// `0x0000_0280..=0x0000_029C` - Interrupt Pending Clear Registers.
+    pub iprr: [Reg<u32, WO>; MAX_FLAGS_WORDS],
+    /// `0x0000_02A0..=0x0000_02FF` - Reserved.
+    _reserved7: [u32; 24],
+    /// `0x0000_0300..=0x0000_031C` - Interrupt Activation Status Registers.
+    pub iactr: [Reg<u32, RO>; MAX_FLAGS_WORDS],
+    /// `0x0000_0320..=0x0000_03FF` - Reserved.
+    _reserved8: [u32; 56],
+    /// `0x0000_0400..=0x0000_04FF` - Interrupt Priority Registers.
+    pub iprior: [Reg<u8>; MAX_SOURCES],
+    /// `0x0000_0500..=0x0000_0D0F` - Reserved.
+    _reserved9: [u32; 516],
+    /// `0x0000_0D10` - System Control Register.
+    pub sctlr: Reg<u32>,
+}
+
+impl<const BASE: usize> PFIC<BASE> {
+    /// Checks if an interrupt source is enabled.
+    #[inline]
+    pub fn is_interrupt_enabled<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).isr[source / u32::BITS as usize].is_set(mask) }
+    }
+
+    /// Enables an interrupt source.
+    ///
+    /// # Safety
+    ///
+    /// Enabling an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn enable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        self.ienr[source / u32::BITS as usize].write(mask);
+    }
+
+    /// Disables an interrupt source.
+    #[inline]
+    pub fn disable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic write with no side effects
+        unsafe { self.irer[source / u32::BITS as usize].write(mask) };
+    }
+
+    /// Checks if an interrupt triggered by a given source is pending.
+    #[inline]
+    pub fn is_interrupt_pending<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).ipr[source / u32::BITS as usize].is_set(mask) }
+    }
+
+    /// Sets the pending flag of an interrupt source.
+    #[inline]
+    pub fn pend<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic write with no side effects
+        unsafe { self.ipsr[source / u32::BITS as usize].write(mask) };
+    }
+
+    /// Clears the pending flag of an interrupt source.
+    #[inline]
+    pub fn unpend<I: InterruptNumber>(&mut self, source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic write with no side effects
+        unsafe { self.iprr[source / u32::BITS as usize].write(mask) };
+    }
+
+    /// Checks if an interrupt source is being serviced.
+    #[inline]
+    pub fn is_interrupt_active<I: InterruptNumber>(source: I) -> bool {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % u32::BITS as usize);
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).iactr[source / u32::BITS as usize].is_set(mask) }
+    }
+
+    /// Returns the priority of an interrupt source.
+    ///
+    /// # Note
+    ///
+    /// Lower values mean higher priorities. Only the upper bits of the priority are implemented,
+    /// and their meaning (preemption or sub-priority) depends on the nesting configuration.
+    #[inline]
+    pub fn priority<I: InterruptNumber>(source: I) -> u8 {
+        let source = usize::from(source.number());
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).iprior[source].read() }
+    }
+
+    /// Sets the priority of an interrupt source.
+    ///
+    /// # Safety
+    ///
+    /// Changing priority levels can break priority-based critical sections and compromise memory safety.
+    #[inline]
+    pub unsafe fn set_priority<I: InterruptNumber>(&mut self, source: I, priority: u8) {
+        let source = usize::from(source.number());
+        self.iprior[source].write(priority);
+    }
+
+    /// Returns the priority threshold of the PFIC.
+    /// A threshold of 0 means that the threshold is disabled.
+    #[inline]
+    pub fn threshold() -> u8 {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).ithresdr.read() as u8 }
+    }
+
+    /// Sets the priority threshold of the PFIC.
+    /// Interrupts with a priority value greater than or equal to the threshold are masked.
+    /// A threshold of 0 disables the threshold.
+    ///
+    /// # Safety
+    ///
+    /// Unmasking an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn set_threshold(&mut self, threshold: u8) {
+        self.ithresdr.write(threshold as u32);
+    }
+
+    /// Returns the current interrupt nesting status.
+    #[inline]
+    pub fn nesting_status() -> u8 {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).gisr.read() as u8 }
+    }
+
+    /// Checks if any interrupt is being serviced.
+    #[inline]
+    pub fn is_any_active() -> bool {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).gisr.is_set(1 << 8) }
+    }
+
+    /// Checks if any interrupt is pending.
+    #[inline]
+    pub fn is_any_pending() -> bool {
+        // SAFETY: atomic read with no side effects
+        unsafe { (*Self::PTR).gisr.is_set(1 << 9) }
+    }
+
+    /// Assigns an interrupt source to a Vector Table Free (VTF) channel and enables it.
+    /// When the interrupt is taken, the core directly jumps to `handler`.
+    ///
+    /// # Safety
+    ///
+    /// `handler` must be the address of a valid interrupt handler, aligned to 2 bytes.
+    #[inline]
+    pub unsafe fn set_vtf<I: InterruptNumber>(
+        &mut self,
+        channel: usize,
+        source: I,
+        handler: usize,
+    ) {
+        assert!(channel < VTF_CHANNELS);
+        self.vtfidr[channel].write(source.number() as u8);
+        self.vtfaddrr[channel].write((handler & !1) as u32 | 1);
+    }
+
+    /// Disables a Vector Table Free (VTF) channel.
+    #[inline]
+    pub fn clear_vtf(&mut self, channel: usize) {
+        assert!(channel < VTF_CHANNELS);
+        // SAFETY: atomic write with no side effects
+        unsafe { self.vtfaddrr[channel].write(0) };
+    }
+
+    /// Requests a system reset.
+    #[inline]
+    pub fn system_reset(&mut self) -> ! {
+        // SAFETY: the system is reset right away
+        unsafe { self.cfgr.write((KEY3 << 16) | RESETSYS) };
+        loop {
+            core::hint::spin_loop();
+        }
+    }
+
+    /// Resets the PFIC peripheral. Namely, it performs the following operations:
+    ///
+    /// - Disables the priority threshold.
+    /// - Disables all the VTF channels.
+    /// - Disables all the interrupt sources and clears their pending flags.
+    /// - Sets the priority of all the interrupt sources to 0.
+    ///
+    /// # Safety
+    ///
+    /// Changing priority levels can break priority-based critical sections and compromise memory safety.
+    #[inline(always)]
+    pub unsafe fn reset<I: InterruptNumber>(&mut self) {
+        self.set_threshold(0);
+        for channel in 0..VTF_CHANNELS {
+            self.clear_vtf(channel);
+        }
+        for source in (0..=I::MAX_INTERRUPT_NUMBER).filter_map(|n| I::try_from(n).ok()) {
+            self.disable_interrupt(source);
+            self.unpend(source);
+            self.set_priority(source, 0);
+        }
+    }
+}
+
+impl<const BASE: usize> Deref for PFIC<BASE> {
+    type Target = RegisterBlock;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        unsafe { &*Self::PTR }
+    }
+}
+
+unsafe impl<const BASE: usize> Send for PFIC<BASE> {}
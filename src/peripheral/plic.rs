@@ -2,25 +2,29 @@
 //!
 //! Specification: <https://github.com/riscv/riscv-plic-spec/blob/master/riscv-plic.adoc>
 
-pub use super::PLIC;
-use crate::register::mie;
+use super::reg::{Reg, RO};
+pub mod future;
+pub mod line;
+
+pub use super::{InterruptNumber, PriorityNumber, PLIC};
+use crate::interrupt::{report_spurious, Spurious};
+use crate::register::{mie, sie};
 use core::ops::Deref;
-use volatile_register::{RO, RW};
 
 /// Maximum number of interrupt sources supported by the PLIC standard.
 const MAX_SOURCES: usize = 1_024;
 /// Maximum number of words needed to represent interrupts with flags.
 const MAX_FLAGS_WORDS: usize = MAX_SOURCES / (u32::BITS as usize);
 /// Maximum number of contexts supported by the PLIC standard.
-const MAX_CONTEXTS: usize = 15_872;
+pub(super) const MAX_CONTEXTS: usize = 15_872;
 
 /// Register block.
 #[repr(C)]
 pub struct RegisterBlock {
     /// `0x0000_0000..=0x0000_0FFC` - Interrupt Priority Register.
-    pub priority: [RW<u32>; MAX_SOURCES],
+    pub priority: [Reg<u32>; MAX_SOURCES],
     /// `0x0000_1000..=0x0000_107C` - Interrupt Pending Register.
-    pub pending: [RO<u32>; MAX_FLAGS_WORDS],
+    pub pending: [Reg<u32, RO>; MAX_FLAGS_WORDS],
     /// `0x0000_1080..=0x0000_1FFC` - Reserved.
     _reserved1: [u32; 0x03e0],
     /// `0x0000_2000..=0x001F_1FFC` - Enable Registers (one per context).
@@ -32,15 +36,15 @@ pub struct RegisterBlock {
 }
 
 /// Interrupt enable for a given context.
-pub type ContextEnable = [RW<u32>; MAX_FLAGS_WORDS];
+pub type ContextEnable = [Reg<u32>; MAX_FLAGS_WORDS];
 
 /// State of a single context.
 #[repr(C)]
 pub struct ContextState {
     /// `0x0000_0000` - Priority Threshold Register.
-    pub threshold: RW<u32>,
+    pub threshold: Reg<u32>,
     /// `0x0000_0004` - Claim/Complete Register.
-    pub claim_complete: RW<u32>,
+    pub claim_complete: Reg<u32>,
     /// `0x0000_0008..=0x0000_0FFC` - Reserved.
     _reserved: [u32; 0x3fe],
 }
@@ -62,6 +66,59 @@ impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
         unsafe { mie::clear_mext() };
     }
 
+    /// Sets the Supervisor External Interrupt bit of the [`crate::register::sie`] CSR.
+    /// This bit must be set for the PLIC to trigger supervisor external interrupts.
+    ///
+    /// # Note
+    ///
+    /// `CONTEXT` must be the S-mode context of the current HART (e.g., `2 * hart + 1` in QEMU `virt`).
+    #[inline]
+    pub fn enable_supervisor() {
+        // SAFETY: atomic CSRRS instruction with no side effects
+        unsafe { sie::set_sext() };
+    }
+
+    /// Clears the Supervisor External Interrupt bit of the [`crate::register::sie`] CSR.
+    /// When cleared, the PLIC does not trigger supervisor external interrupts.
+    #[inline]
+    pub fn disable_supervisor() {
+        // SAFETY: atomic CSRRC instruction with no side effects
+        unsafe { sie::clear_sext() };
+    }
+
+    /// Initializes the PLIC context for serving supervisor external interrupts.
+    /// Namely, it sets the priority threshold and enables supervisor external interrupts.
+    ///
+    /// # Safety
+    ///
+    /// Unmasking an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn init_supervisor<P: PriorityNumber>(&mut self, threshold: P) {
+        self.set_threshold(threshold);
+        Self::enable_supervisor();
+    }
+
+    /// Serves all the pending interrupts of the PLIC context.
+    ///
+    /// It claims pending interrupts one by one, calls `handler` with each of them,
+    /// and marks them as complete. It returns the number of served interrupts.
+    /// Call it from the external interrupt handler (e.g., on `SupervisorExternal` in S-mode).
+    ///
+    /// If no interrupt can be claimed, the interrupt is reported via [`report_spurious`].
+    #[inline]
+    pub fn service<I: InterruptNumber, F: FnMut(I)>(mut handler: F) -> usize {
+        let mut served = 0;
+        while let Some(source) = Self::claim::<I>() {
+            handler(source);
+            Self::complete(source);
+            served += 1;
+        }
+        if served == 0 {
+            report_spurious(Spurious::Claim { context: CONTEXT });
+        }
+        served
+    }
+
     /// Returns the priority level associated to a given interrupt source.
     #[inline]
     pub fn priority<I: InterruptNumber, P: PriorityNumber>(source: I) -> P {
@@ -123,7 +180,7 @@ impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
     pub unsafe fn enable_interrupt<I: InterruptNumber>(&mut self, source: I) {
         let source = usize::from(source.number());
         let mask: u32 = 1 << (source % MAX_FLAGS_WORDS);
-        self.enables[CONTEXT][source / MAX_FLAGS_WORDS].modify(|value| value | mask);
+        self.enables[CONTEXT][source / MAX_FLAGS_WORDS].set_bits(mask);
     }
 
     /// Disables an interrupt source for the PLIC context.
@@ -135,7 +192,7 @@ impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
     pub unsafe fn disable_interrupt<I: InterruptNumber>(&mut self, source: I) {
         let source = usize::from(source.number());
         let mask: u32 = 1 << (source % MAX_FLAGS_WORDS);
-        self.enables[CONTEXT][source / MAX_FLAGS_WORDS].modify(|value| value & !mask);
+        self.enables[CONTEXT][source / MAX_FLAGS_WORDS].clear_bits(mask);
     }
 
     /// Returns the priority threshold of the PLIC context.
@@ -215,66 +272,3 @@ impl<const BASE: usize, const CONTEXT: usize> Deref for PLIC<BASE, CONTEXT> {
 }
 
 unsafe impl<const BASE: usize, const CONTEXT: usize> Send for PLIC<BASE, CONTEXT> {}
-
-/// Trait for enums of interrupt numbers.
-///
-/// This trait should be implemented by a peripheral access crate (PAC)
-/// on its enum of available external interrupts for a specific device.
-/// Each variant must convert to a `u16` of its interrupt number.
-///
-/// # Note
-///
-/// Recall that the interrupt number `0` is reserved as "no interrupt".
-///
-/// # Safety
-///
-/// This trait must only be implemented on enums of external interrupts. Each
-/// enum variant must represent a distinct value (no duplicates are permitted),
-/// and must always return the same value (do not change at runtime).
-/// All the interrupt numbers must be less than or equal to `MAX_INTERRUPT_NUMBER`.
-/// `MAX_INTERRUPT_NUMBER` must coincide with the highest allowed interrupt number.
-///
-/// These requirements ensure safe nesting of critical sections.
-pub unsafe trait InterruptNumber: Copy {
-    /// Highest number assigned to an interrupt source.
-    const MAX_INTERRUPT_NUMBER: u16;
-
-    /// Converts an interrupt source to its corresponding number.
-    fn number(self) -> u16;
-
-    /// Tries to convert a number to a valid interrupt source.
-    /// If the conversion fails, it returns an error with the number back.
-    fn try_from(value: u16) -> Result<Self, u16>;
-}
-
-/// Trait for enums of interrupt priority numbers.
-///
-/// This trait should be implemented by a peripheral access crate (PAC)
-/// on its enum of available priority numbers for a specific device.
-/// Each variant must convert to a `u8` of its priority level.
-///
-/// # Note
-///
-/// Recall that the priority number `0` is reserved as "never interrupt".
-///
-/// # Safety
-///
-/// This trait must only be implemented on enums of priority levels. Each
-/// enum variant must represent a distinct value (no duplicates are permitted),
-/// and must always return the same value (do not change at runtime).
-/// There must be a valid priority number set to 0 (i.e., never interrupt).
-/// All the priority level numbers must be less than or equal to `MAX_PRIORITY_NUMBER`.
-/// `MAX_PRIORITY_NUMBER` must coincide with the highest allowed priority number.
-///
-/// These requirements ensure safe nesting of critical sections.
-pub unsafe trait PriorityNumber: Copy {
-    /// Number assigned to the highest priority level.
-    const MAX_PRIORITY_NUMBER: u8;
-
-    /// Converts a priority level to its corresponding number.
-    fn number(self) -> u8;
-
-    /// Tries to convert a number to a valid priority level.
-    /// If the conversion fails, it returns an error with the number back.
-    fn try_from(value: u8) -> Result<Self, u8>;
-}
@@ -0,0 +1,212 @@
+//! Futures for awaiting PLIC interrupt sources.
+//!
+//! An [`InterruptFuture`] unmasks its interrupt source for the PLIC context when first polled
+//! and completes once the external interrupt handler reports the source through [`PLIC::wake`].
+//! Wakers are stored in a user-provided [`InterruptWakers`] table, usually a `static`.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::peripheral::{plic::future::InterruptWakers, PLIC};
+//! # use riscv::peripheral::InterruptNumber;
+//! # #[derive(Clone, Copy)]
+//! # pub enum Interrupt { Uart0 = 1 }
+//! # unsafe impl InterruptNumber for Interrupt {
+//! #     const MAX_INTERRUPT_NUMBER: u16 = 1;
+//! #     fn number(self) -> u16 { self as u16 }
+//! #     fn try_from(value: u16) -> Result<Self, u16> {
+//! #         match value { 1 => Ok(Self::Uart0), _ => Err(value) }
+//! #     }
+//! # }
+//!
+//! type Plic = PLIC<0x0C00_0000, 0>;
+//! static WAKERS: InterruptWakers<64> = InterruptWakers::new();
+//!
+//! async fn wait_uart() {
+//!     Plic::wait(&WAKERS, Interrupt::Uart0).await;
+//! }
+//!
+//! // Machine external interrupt handler
+//! fn machine_external() {
+//!     while let Some(source) = Plic::claim::<Interrupt>() {
+//!         Plic::wake(&WAKERS, source);
+//!         Plic::complete(source);
+//!     }
+//! }
+//! ```
+
+use super::{InterruptNumber, MAX_FLAGS_WORDS, PLIC};
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+use critical_section::Mutex;
+
+struct Slot {
+    waker: Option<Waker>,
+    fired: bool,
+}
+
+const EMPTY_SLOT: Slot = Slot {
+    waker: None,
+    fired: false,
+};
+
+/// Table of wakers for the first `N` interrupt sources.
+pub struct InterruptWakers<const N: usize> {
+    slots: Mutex<RefCell<[Slot; N]>>,
+}
+
+impl<const N: usize> InterruptWakers<N> {
+    /// Creates a new, empty waker table.
+    pub const fn new() -> Self {
+        Self {
+            slots: Mutex::new(RefCell::new([EMPTY_SLOT; N])),
+        }
+    }
+}
+
+impl<const N: usize> Default for InterruptWakers<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Future that completes when an interrupt source fires.
+///
+/// The interrupt source is masked again when the future completes or is dropped.
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct InterruptFuture<
+    'a,
+    const BASE: usize,
+    const CONTEXT: usize,
+    I: InterruptNumber,
+    const N: usize,
+> {
+    wakers: &'a InterruptWakers<N>,
+    source: I,
+    armed: bool,
+}
+
+impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
+    /// Returns a future that completes on the next interrupt of a given source.
+    ///
+    /// The interrupt number of the source must be lower than `N`.
+    #[inline]
+    pub fn wait<I: InterruptNumber, const N: usize>(
+        wakers: &InterruptWakers<N>,
+        source: I,
+    ) -> InterruptFuture<'_, BASE, CONTEXT, I, N> {
+        assert!(usize::from(source.number()) < N, "no waker slot for source");
+        InterruptFuture {
+            wakers,
+            source,
+            armed: false,
+        }
+    }
+
+    /// Handler hook for futures created with [`Self::wait`].
+    ///
+    /// If a task is awaiting the interrupt source, it masks the source for the context and wakes the task.
+    /// Call it from the external interrupt handler with every claimed source, before completing it.
+    /// It returns `true` if a task was awaiting the source.
+    #[inline]
+    pub fn wake<I: InterruptNumber, const N: usize>(
+        wakers: &InterruptWakers<N>,
+        source: I,
+    ) -> bool {
+        let n = usize::from(source.number());
+        if n >= N {
+            return false;
+        }
+        critical_section::with(|cs| {
+            let mut slots = wakers.slots.borrow(cs).borrow_mut();
+            let slot = &mut slots[n];
+            match slot.waker.take() {
+                Some(waker) => {
+                    // SAFETY: read-modify-write within a critical section
+                    unsafe { Self::mask(source) };
+                    slot.fired = true;
+                    waker.wake();
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+
+    /// Disables an interrupt source for the context without requiring a PLIC instance.
+    ///
+    /// # Safety
+    ///
+    /// Must be called within a critical section.
+    #[inline]
+    unsafe fn mask<I: InterruptNumber>(source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % MAX_FLAGS_WORDS);
+        (*Self::PTR).enables[CONTEXT][source / MAX_FLAGS_WORDS].clear_bits(mask);
+    }
+
+    /// Enables an interrupt source for the context without requiring a PLIC instance.
+    ///
+    /// # Safety
+    ///
+    /// Must be called within a critical section.
+    #[inline]
+    unsafe fn unmask<I: InterruptNumber>(source: I) {
+        let source = usize::from(source.number());
+        let mask: u32 = 1 << (source % MAX_FLAGS_WORDS);
+        (*Self::PTR).enables[CONTEXT][source / MAX_FLAGS_WORDS].set_bits(mask);
+    }
+}
+
+// The future holds no self-references
+impl<const BASE: usize, const CONTEXT: usize, I: InterruptNumber, const N: usize> Unpin
+    for InterruptFuture<'_, BASE, CONTEXT, I, N>
+{
+}
+
+impl<const BASE: usize, const CONTEXT: usize, I: InterruptNumber, const N: usize> Future
+    for InterruptFuture<'_, BASE, CONTEXT, I, N>
+{
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let n = usize::from(this.source.number());
+        critical_section::with(|cs| {
+            let mut slots = this.wakers.slots.borrow(cs).borrow_mut();
+            let slot = &mut slots[n];
+            if this.armed && slot.fired {
+                slot.fired = false;
+                this.armed = false;
+                return Poll::Ready(());
+            }
+            slot.waker = Some(cx.waker().clone());
+            if !this.armed {
+                slot.fired = false;
+                this.armed = true;
+                // SAFETY: read-modify-write within a critical section
+                unsafe { PLIC::<BASE, CONTEXT>::unmask(this.source) };
+            }
+            Poll::Pending
+        })
+    }
+}
+
+impl<const BASE: usize, const CONTEXT: usize, I: InterruptNumber, const N: usize> Drop
+    for InterruptFuture<'_, BASE, CONTEXT, I, N>
+{
+    fn drop(&mut self) {
+        if self.armed {
+            let n = usize::from(self.source.number());
+            critical_section::with(|cs| {
+                // SAFETY: read-modify-write within a critical section
+                unsafe { PLIC::<BASE, CONTEXT>::mask(self.source) };
+                let mut slots = self.wakers.slots.borrow(cs).borrow_mut();
+                slots[n].waker = None;
+                slots[n].fired = false;
+            });
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Per-source handles of the PLIC.
+//!
+//! A [`PlicLine`] gives a driver access to exactly one interrupt source of a PLIC context.
+//! Thus, HAL drivers can take ownership of their interrupt line instead of the whole PLIC.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::peripheral::{plic::line::PlicLine, PLIC};
+//! # use riscv::peripheral::{InterruptNumber, PriorityNumber};
+//! # #[derive(Clone, Copy)]
+//! # pub enum Interrupt { Uart0 = 1 }
+//! # unsafe impl InterruptNumber for Interrupt {
+//! #     const MAX_INTERRUPT_NUMBER: u16 = 1;
+//! #     fn number(self) -> u16 { self as u16 }
+//! #     fn try_from(value: u16) -> Result<Self, u16> {
+//! #         match value { 1 => Ok(Self::Uart0), _ => Err(value) }
+//! #     }
+//! # }
+//! # #[derive(Clone, Copy)]
+//! # pub enum Priority { P0 = 0, P1 = 1 }
+//! # unsafe impl PriorityNumber for Priority {
+//! #     const MAX_PRIORITY_NUMBER: u8 = 1;
+//! #     fn number(self) -> u8 { self as u8 }
+//! #     fn try_from(value: u8) -> Result<Self, u8> {
+//! #         match value { 0 => Ok(Self::P0), 1 => Ok(Self::P1), _ => Err(value) }
+//! #     }
+//! # }
+//!
+//! struct Uart {
+//!     irq: PlicLine<0x0C00_0000, 0, Interrupt>,
+//! }
+//!
+//! impl Uart {
+//!     fn new(irq: PlicLine<0x0C00_0000, 0, Interrupt>) -> Self {
+//!         let mut uart = Self { irq };
+//!         unsafe {
+//!             uart.irq.set_priority(Priority::P1);
+//!             uart.irq.enable();
+//!         }
+//!         uart
+//!     }
+//! }
+//!
+//! let mut plic = PLIC::<0x0C00_0000, 0>::new();
+//! // SAFETY: the UART driver is the only owner of its interrupt line
+//! let uart = Uart::new(unsafe { plic.line(Interrupt::Uart0) });
+//! ```
+
+use super::{InterruptNumber, PriorityNumber, PLIC};
+
+/// Handle to a single interrupt source of a PLIC context.
+pub struct PlicLine<const BASE: usize, const CONTEXT: usize, I: InterruptNumber> {
+    plic: PLIC<BASE, CONTEXT>,
+    source: I,
+}
+
+impl<const BASE: usize, const CONTEXT: usize> PLIC<BASE, CONTEXT> {
+    /// Returns a handle to a given interrupt source of the PLIC context.
+    ///
+    /// # Safety
+    ///
+    /// There must be at most one handle to each interrupt source of the context.
+    /// Handles to different sources still perform read-modify-write operations on
+    /// shared enable registers (see [`PlicLine::enable`]).
+    #[inline]
+    pub unsafe fn line<I: InterruptNumber>(&mut self, source: I) -> PlicLine<BASE, CONTEXT, I> {
+        PlicLine {
+            plic: Self::new(),
+            source,
+        }
+    }
+}
+
+impl<const BASE: usize, const CONTEXT: usize, I: InterruptNumber> PlicLine<BASE, CONTEXT, I> {
+    /// Returns the interrupt source of the handle.
+    #[inline]
+    pub fn source(&self) -> I {
+        self.source
+    }
+
+    /// Enables the interrupt source for the PLIC context.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    /// Additionally, Enabling an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn enable(&mut self) {
+        self.plic.enable_interrupt(self.source);
+    }
+
+    /// Disables the interrupt source for the PLIC context.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline]
+    pub unsafe fn disable(&mut self) {
+        self.plic.disable_interrupt(self.source);
+    }
+
+    /// Checks if the interrupt source is enabled for the PLIC context.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        PLIC::<BASE, CONTEXT>::is_interrupt_enabled(self.source)
+    }
+
+    /// Checks if an interrupt triggered by the source is pending.
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        PLIC::<BASE, CONTEXT>::is_interrupt_pending(self.source)
+    }
+
+    /// Returns the priority level of the interrupt source.
+    #[inline]
+    pub fn priority<P: PriorityNumber>(&self) -> P {
+        PLIC::<BASE, CONTEXT>::priority(self.source)
+    }
+
+    /// Sets the priority level of the interrupt source.
+    ///
+    /// # Note
+    ///
+    /// Interrupt source priorities are shared among all the contexts of the PLIC.
+    ///
+    /// # Safety
+    ///
+    /// Changing priority levels can break priority-based critical sections and compromise memory safety.
+    #[inline]
+    pub unsafe fn set_priority<P: PriorityNumber>(&mut self, priority: P) {
+        self.plic.set_priority(self.source, priority);
+    }
+
+    /// Marks a claimed interrupt of the source as complete for the PLIC context.
+    #[inline]
+    pub fn complete(&self) {
+        PLIC::<BASE, CONTEXT>::complete(self.source);
+    }
+}
@@ -0,0 +1,23 @@
+//! Core peripherals of the QEMU `virt` machine.
+//!
+//! # Note
+//!
+//! This module requires the `qemu-virt` feature.
+//!
+//! The PLIC interface corresponds to the M-mode context of HART 0.
+//! Other HARTs and privilege modes can use [`PLIC`](super::PLIC) with the context number
+//! `2 * hart` for M-mode, and `2 * hart + 1` for S-mode.
+
+/// Base address of the CLINT peripheral.
+pub const CLINT_BASE: usize = 0x0200_0000;
+
+/// Base address of the PLIC peripheral.
+pub const PLIC_BASE: usize = 0x0C00_0000;
+
+/// Frequency of the `mtime` register, in Hertz.
+pub const TIMEBASE_FREQUENCY: u32 = 10_000_000;
+
+crate::riscv_peripherals! {
+    CLINT { base: CLINT_BASE },
+    PLIC { base: PLIC_BASE, context: 0 },
+}
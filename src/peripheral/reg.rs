@@ -0,0 +1,220 @@
+//! Raw access to memory-mapped registers.
+//!
+//! [`Reg`] wraps a memory-mapped register of type `T` and performs volatile accesses.
+//! The access type `A` ([`RW`], [`RO`] or [`WO`]) restricts the available operations.
+//!
+//! # Audit
+//!
+//! With the `audit` feature, every read and write of a [`Reg`] is reported to the hook installed
+//! with [`set_audit_hook`], so hardware bring-up sessions can capture the exact MMIO traffic.
+//! Accesses performed by the hook itself (e.g., writing the log to a UART) are not reported.
+//! The hook runs within a critical section, so it must be short and must not wait for interrupts.
+
+use core::cell::UnsafeCell;
+use core::marker::PhantomData;
+use core::ops::{BitAnd, BitOr, Not};
+
+/// Marker trait for readable registers.
+pub trait Read {}
+
+/// Marker trait for writable registers.
+pub trait Write {}
+
+/// Read-write access.
+pub struct RW;
+impl Read for RW {}
+impl Write for RW {}
+
+/// Read-only access.
+pub struct RO;
+impl Read for RO {}
+
+/// Write-only access.
+pub struct WO;
+impl Write for WO {}
+
+/// Memory-mapped register.
+#[repr(transparent)]
+pub struct Reg<T: Copy, A = RW> {
+    value: UnsafeCell<T>,
+    _access: PhantomData<A>,
+}
+
+impl<T: Copy, A> Reg<T, A> {
+    /// Returns a raw pointer to the register.
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *mut T {
+        self.value.get()
+    }
+}
+
+impl<T: Copy, A: Read> Reg<T, A> {
+    /// Performs a volatile read of the register.
+    #[inline(always)]
+    pub fn read(&self) -> T {
+        // SAFETY: the register is valid for reads
+        let value = unsafe { self.value.get().read_volatile() };
+        #[cfg(feature = "audit")]
+        audit(Access::new(self.as_ptr(), value, false));
+        value
+    }
+}
+
+impl<T: Copy, A: Write> Reg<T, A> {
+    /// Performs a volatile write of the register.
+    ///
+    /// # Safety
+    ///
+    /// Writing to a peripheral register may have side effects that compromise memory safety.
+    #[inline(always)]
+    pub unsafe fn write(&self, value: T) {
+        self.value.get().write_volatile(value);
+        #[cfg(feature = "audit")]
+        audit(Access::new(self.as_ptr(), value, true));
+    }
+}
+
+impl<T: Copy, A: Read + Write> Reg<T, A> {
+    /// Performs a read-modify-write operation of the register.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline(always)]
+    pub unsafe fn modify<F: FnOnce(T) -> T>(&self, f: F) {
+        self.write(f(self.read()));
+    }
+}
+
+impl<T, A: Read> Reg<T, A>
+where
+    T: Copy + BitAnd<Output = T> + PartialEq,
+{
+    /// Checks if all the bits of `mask` are set.
+    #[inline(always)]
+    pub fn is_set(&self, mask: T) -> bool {
+        (self.read() & mask) == mask
+    }
+}
+
+impl<T, A: Read + Write> Reg<T, A>
+where
+    T: Copy + BitAnd<Output = T> + BitOr<Output = T> + Not<Output = T>,
+{
+    /// Sets the bits of `mask`.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline(always)]
+    pub unsafe fn set_bits(&self, mask: T) {
+        self.modify(|value| value | mask);
+    }
+
+    /// Clears the bits of `mask`.
+    ///
+    /// # Safety
+    ///
+    /// It performs non-atomic read-modify-write operations, which may lead to undefined behavior.
+    #[inline(always)]
+    pub unsafe fn clear_bits(&self, mask: T) {
+        self.modify(|value| value & !mask);
+    }
+}
+
+/// Register access reported to the audit hook.
+///
+/// # Note
+///
+/// This structure requires the `audit` feature.
+#[cfg(feature = "audit")]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Access {
+    /// Address of the register.
+    pub address: usize,
+    /// Value read or written, zero-extended to 64 bits.
+    pub value: u64,
+    /// Size of the access, in bytes.
+    pub size: usize,
+    /// Whether the access is a write (`true`) or a read (`false`).
+    pub write: bool,
+}
+
+#[cfg(feature = "audit")]
+impl Access {
+    #[inline]
+    fn new<T: Copy>(ptr: *mut T, value: T, write: bool) -> Self {
+        let size = core::mem::size_of::<T>().min(8);
+        let mut raw = 0u64;
+        // SAFETY: RISC-V is little-endian, and at most 8 bytes are copied
+        unsafe {
+            core::ptr::copy_nonoverlapping(
+                &value as *const T as *const u8,
+                &mut raw as *mut u64 as *mut u8,
+                size,
+            )
+        };
+        Self {
+            address: ptr as usize,
+            value: raw,
+            size,
+            write,
+        }
+    }
+}
+
+/// Audit hook for register accesses.
+#[cfg(feature = "audit")]
+pub type AuditHook = fn(Access);
+
+#[cfg(feature = "audit")]
+static AUDIT_HOOK: critical_section::Mutex<core::cell::Cell<Option<AuditHook>>> =
+    critical_section::Mutex::new(core::cell::Cell::new(None));
+
+/// Whether the audit hook is running. Only the context that runs the hook can observe it set,
+/// as the hook runs within a critical section.
+#[cfg(feature = "audit")]
+static AUDIT_ACTIVE: critical_section::Mutex<core::cell::Cell<bool>> =
+    critical_section::Mutex::new(core::cell::Cell::new(false));
+
+/// Installs the audit hook and returns the previous one, if any.
+///
+/// # Note
+///
+/// This function requires the `audit` feature.
+#[cfg(feature = "audit")]
+#[inline]
+pub fn set_audit_hook(hook: AuditHook) -> Option<AuditHook> {
+    critical_section::with(|cs| AUDIT_HOOK.borrow(cs).replace(Some(hook)))
+}
+
+/// Removes the audit hook and returns it, if any.
+///
+/// # Note
+///
+/// This function requires the `audit` feature.
+#[cfg(feature = "audit")]
+#[inline]
+pub fn clear_audit_hook() -> Option<AuditHook> {
+    critical_section::with(|cs| AUDIT_HOOK.borrow(cs).take())
+}
+
+/// Reports an access to the audit hook.
+///
+/// The hook runs within a critical section, so accesses from interrupt handlers or other HARTs
+/// wait for it to complete and are reported afterwards. Its own accesses are not reported.
+#[cfg(feature = "audit")]
+#[inline]
+fn audit(access: Access) {
+    critical_section::with(|cs| {
+        let active = AUDIT_ACTIVE.borrow(cs);
+        if active.get() {
+            return;
+        }
+        if let Some(hook) = AUDIT_HOOK.borrow(cs).get() {
+            active.set(true);
+            hook(access);
+            active.set(false);
+        }
+    });
+}
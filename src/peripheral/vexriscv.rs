@@ -0,0 +1,82 @@
+//! VexRiscv external interrupt controller.
+//!
+//! VexRiscv cores with the external interrupt array plugin (e.g., in LiteX SoCs)
+//! expose up to XLEN external interrupt lines through custom CSRs.
+//! See [`crate::register::vexriscv`] for the raw CSR access.
+//!
+//! The functions that take an interrupt line panic if its number is not lower than XLEN.
+
+pub use super::{InterruptNumber, VexRiscvIntc};
+use crate::register::{mie, vexriscv};
+
+impl VexRiscvIntc {
+    /// Sets the Machine External Interrupt bit of the [`crate::register::mie`] CSR.
+    /// This bit must be set for unmasked interrupt lines to trigger machine external interrupts.
+    #[inline]
+    pub fn enable() {
+        // SAFETY: atomic CSRRS instruction with no side effects
+        unsafe { mie::set_mext() };
+    }
+
+    /// Clears the Machine External Interrupt bit of the [`crate::register::mie`] CSR.
+    #[inline]
+    pub fn disable() {
+        // SAFETY: atomic CSRRC instruction with no side effects
+        unsafe { mie::clear_mext() };
+    }
+
+    /// Checks if an interrupt line is unmasked.
+    #[inline]
+    pub fn is_interrupt_enabled<I: InterruptNumber>(source: I) -> bool {
+        let mask = mask(source);
+        (vexriscv::mmask::read() & mask) == mask
+    }
+
+    /// Unmasks an interrupt line.
+    ///
+    /// # Safety
+    ///
+    /// Enabling an interrupt source can break mask-based critical sections.
+    #[inline]
+    pub unsafe fn enable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        vexriscv::mmask::set(mask(source));
+    }
+
+    /// Masks an interrupt line.
+    #[inline]
+    pub fn disable_interrupt<I: InterruptNumber>(&mut self, source: I) {
+        vexriscv::mmask::clear(mask(source));
+    }
+
+    /// Checks if an interrupt line is pending, regardless of its mask.
+    #[inline]
+    pub fn is_interrupt_pending<I: InterruptNumber>(source: I) -> bool {
+        let mask = mask(source);
+        (vexriscv::mpending::read() & mask) == mask
+    }
+
+    /// Returns the lowest-numbered interrupt line that is both pending and unmasked.
+    /// If no such interrupt line exists, it returns [`None`].
+    #[inline]
+    pub fn pending<I: InterruptNumber>() -> Option<I> {
+        let active = vexriscv::mpending::read() & vexriscv::mmask::read();
+        match active {
+            0 => None,
+            a => I::try_from(a.trailing_zeros() as u16).ok(),
+        }
+    }
+
+    /// Masks all the interrupt lines.
+    #[inline]
+    pub fn reset(&mut self) {
+        vexriscv::mmask::clear(usize::MAX);
+    }
+}
+
+/// Returns the bit mask of an interrupt line.
+#[inline]
+fn mask<I: InterruptNumber>(source: I) -> usize {
+    let number = usize::from(source.number());
+    assert!(number < usize::BITS as usize, "invalid interrupt line");
+    1 << number
+}
@@ -0,0 +1,183 @@
+//! dcsr register
+//!
+//! This register is only accessible from Debug Mode.
+
+use bit_field::BitField;
+
+/// dcsr register
+#[derive(Clone, Copy, Debug)]
+pub struct Dcsr {
+    bits: usize,
+}
+
+/// Cause for entering Debug Mode
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Cause {
+    /// An `ebreak` instruction was executed
+    Ebreak = 1,
+    /// A trigger module fired with action 1
+    Trigger = 2,
+    /// The debugger requested entry to Debug Mode
+    HaltReq = 3,
+    /// The hart single stepped
+    Step = 4,
+    /// The hart halted directly out of reset
+    ResetHaltReq = 5,
+    /// The hart halted because it is part of a halt group
+    Group = 6,
+}
+
+/// Privilege level the hart was operating in when Debug Mode was entered
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Prv {
+    User = 0,
+    Supervisor = 1,
+    Machine = 3,
+}
+
+impl Dcsr {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Privilege level the hart was operating in when Debug Mode was entered
+    ///
+    /// The hart returns to this privilege level when leaving Debug Mode.
+    /// Returns [`None`] if the privilege level is reserved by the specification.
+    #[inline]
+    pub fn prv(&self) -> Option<Prv> {
+        match self.bits.get_bits(0..2) {
+            0b00 => Some(Prv::User),
+            0b01 => Some(Prv::Supervisor),
+            0b11 => Some(Prv::Machine),
+            _ => None,
+        }
+    }
+
+    /// Single step
+    ///
+    /// When set and not in Debug Mode, the hart only executes a single instruction and then enters Debug Mode.
+    #[inline]
+    pub fn step(&self) -> bool {
+        self.bits.get_bit(2)
+    }
+
+    /// Non-Maskable Interrupt Pending
+    #[inline]
+    pub fn nmip(&self) -> bool {
+        self.bits.get_bit(3)
+    }
+
+    /// Use `mstatus.MPRV` in Debug Mode
+    #[inline]
+    pub fn mprven(&self) -> bool {
+        self.bits.get_bit(4)
+    }
+
+    /// Cause for entering Debug Mode
+    ///
+    /// Returns [`None`] if the cause is reserved by the specification.
+    #[inline]
+    pub fn cause(&self) -> Option<Cause> {
+        match self.bits.get_bits(6..9) {
+            1 => Some(Cause::Ebreak),
+            2 => Some(Cause::Trigger),
+            3 => Some(Cause::HaltReq),
+            4 => Some(Cause::Step),
+            5 => Some(Cause::ResetHaltReq),
+            6 => Some(Cause::Group),
+            _ => None,
+        }
+    }
+
+    /// Stop timers (e.g., `mtime`) while in Debug Mode
+    #[inline]
+    pub fn stoptime(&self) -> bool {
+        self.bits.get_bit(9)
+    }
+
+    /// Stop counters (e.g., `mcycle` and `minstret`) while in Debug Mode
+    #[inline]
+    pub fn stopcount(&self) -> bool {
+        self.bits.get_bit(10)
+    }
+
+    /// Enable interrupts during single stepping
+    #[inline]
+    pub fn stepie(&self) -> bool {
+        self.bits.get_bit(11)
+    }
+
+    /// `ebreak` instructions in U-mode enter Debug Mode
+    #[inline]
+    pub fn ebreaku(&self) -> bool {
+        self.bits.get_bit(12)
+    }
+
+    /// `ebreak` instructions in S-mode enter Debug Mode
+    #[inline]
+    pub fn ebreaks(&self) -> bool {
+        self.bits.get_bit(13)
+    }
+
+    /// `ebreak` instructions in M-mode enter Debug Mode
+    #[inline]
+    pub fn ebreakm(&self) -> bool {
+        self.bits.get_bit(15)
+    }
+
+    /// Debug support version
+    ///
+    /// `0` means there is no external debug support, and `4` means external
+    /// debug support exists as described in the RISC-V Debug Specification.
+    #[inline]
+    pub fn xdebugver(&self) -> usize {
+        self.bits.get_bits(28..32)
+    }
+}
+
+read_csr_as!(Dcsr, 0x7B0);
+write_csr!(0x7B0);
+set!(0x7B0);
+clear!(0x7B0);
+
+set_clear_csr_imm!(
+    /// Single step
+    , 0x7B0, set_step, clear_step, 1 << 2);
+set_clear_csr_imm!(
+    /// Use `mstatus.MPRV` in Debug Mode
+    , 0x7B0, set_mprven, clear_mprven, 1 << 4);
+set_clear_csr!(
+    /// Stop timers while in Debug Mode
+    , set_stoptime, clear_stoptime, 1 << 9);
+set_clear_csr!(
+    /// Stop counters while in Debug Mode
+    , set_stopcount, clear_stopcount, 1 << 10);
+set_clear_csr!(
+    /// Enable interrupts during single stepping
+    , set_stepie, clear_stepie, 1 << 11);
+set_clear_csr!(
+    /// `ebreak` instructions in U-mode enter Debug Mode
+    , set_ebreaku, clear_ebreaku, 1 << 12);
+set_clear_csr!(
+    /// `ebreak` instructions in S-mode enter Debug Mode
+    , set_ebreaks, clear_ebreaks, 1 << 13);
+set_clear_csr!(
+    /// `ebreak` instructions in M-mode enter Debug Mode
+    , set_ebreakm, clear_ebreakm, 1 << 15);
+
+/// Privilege level the hart returns to when leaving Debug Mode
+#[inline]
+pub unsafe fn set_prv(prv: Prv) {
+    let mut value = _read();
+    value.set_bits(0..2, prv as usize);
+    _write(value);
+}
@@ -75,6 +75,12 @@ pub enum RoundingMode {
 }
 
 impl FCSR {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: u32) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> u32 {
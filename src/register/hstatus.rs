@@ -0,0 +1,106 @@
+//! hstatus register
+//!
+//! Hypervisor Status register of the hypervisor extension.
+
+use bit_field::BitField;
+
+/// hstatus register
+#[derive(Clone, Copy, Debug)]
+pub struct Hstatus {
+    bits: usize,
+}
+
+impl Hstatus {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Guest Virtual Address
+    ///
+    /// Set when a trap into HS-mode writes a guest virtual address to `stval`.
+    #[inline]
+    pub fn gva(&self) -> bool {
+        self.bits.get_bit(6)
+    }
+
+    /// Supervisor Previous Virtualization mode
+    ///
+    /// Set when a trap into HS-mode is taken from VS-mode or VU-mode.
+    /// When set, `sret` returns to a virtualized mode.
+    #[inline]
+    pub fn spv(&self) -> bool {
+        self.bits.get_bit(7)
+    }
+
+    /// Supervisor Previous Virtual Privilege
+    ///
+    /// Privilege of the virtualized mode when a trap is taken from it (`true` for VS-mode and `false` for VU-mode).
+    #[inline]
+    pub fn spvp(&self) -> bool {
+        self.bits.get_bit(8)
+    }
+
+    /// Hypervisor in U-mode
+    ///
+    /// When set, the hypervisor load/store instructions are also allowed in U-mode.
+    #[inline]
+    pub fn hu(&self) -> bool {
+        self.bits.get_bit(9)
+    }
+
+    /// Virtual Trap Virtual Memory
+    #[inline]
+    pub fn vtvm(&self) -> bool {
+        self.bits.get_bit(20)
+    }
+
+    /// Virtual Timeout Wait
+    #[inline]
+    pub fn vtw(&self) -> bool {
+        self.bits.get_bit(21)
+    }
+
+    /// Virtual Trap SRET
+    #[inline]
+    pub fn vtsr(&self) -> bool {
+        self.bits.get_bit(22)
+    }
+}
+
+read_csr_as!(Hstatus, 0x600);
+write_csr!(0x600);
+set!(0x600);
+clear!(0x600);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr!(
+    /// Supervisor Previous Virtualization mode
+    , set_spv, clear_spv, 1 << 7);
+set_clear_csr!(
+    /// Supervisor Previous Virtual Privilege
+    , set_spvp, clear_spvp, 1 << 8);
+set_clear_csr!(
+    /// Hypervisor in U-mode
+    , set_hu, clear_hu, 1 << 9);
+set_clear_csr!(
+    /// Virtual Trap Virtual Memory
+    , set_vtvm, clear_vtvm, 1 << 20);
+set_clear_csr!(
+    /// Virtual Timeout Wait
+    , set_vtw, clear_vtw, 1 << 21);
+set_clear_csr!(
+    /// Virtual Trap SRET
+    , set_vtsr, clear_vtsr, 1 << 22);
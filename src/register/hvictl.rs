@@ -0,0 +1,101 @@
+//! hvictl register
+//!
+//! Hypervisor Virtual Interrupt Control register of the Advanced Interrupt Architecture (AIA).
+//! It lets a hypervisor inject a virtual interrupt into VS-mode.
+
+use bit_field::BitField;
+
+/// hvictl register
+#[derive(Clone, Copy, Debug)]
+pub struct Hvictl {
+    bits: usize,
+}
+
+impl Hvictl {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Virtual Trap Interrupt control
+    ///
+    /// When set, attempts from VS-mode to explicitly access `sip` and `sie` raise virtual instruction exceptions.
+    #[inline]
+    pub fn vti(&self) -> bool {
+        self.bits.get_bit(30)
+    }
+
+    /// Interrupt Identity of the virtual interrupt
+    #[inline]
+    pub fn iid(&self) -> u16 {
+        self.bits.get_bits(16..28) as u16
+    }
+
+    /// Default Priority Rank
+    ///
+    /// When `iprio` is zero, it selects whether the virtual interrupt has a lower (`false`)
+    /// or higher (`true`) priority than supervisor external interrupts.
+    #[inline]
+    pub fn dpr(&self) -> bool {
+        self.bits.get_bit(9)
+    }
+
+    /// Interrupt Priority Mode
+    ///
+    /// When set, `iprio` is the priority of the virtual interrupt.
+    /// Otherwise, the virtual interrupt has priority 256.
+    #[inline]
+    pub fn ipriom(&self) -> bool {
+        self.bits.get_bit(8)
+    }
+
+    /// Interrupt Priority of the virtual interrupt
+    #[inline]
+    pub fn iprio(&self) -> u8 {
+        self.bits.get_bits(0..8) as u8
+    }
+}
+
+read_csr_as!(Hvictl, 0x609);
+write_csr!(0x609);
+set!(0x609);
+clear!(0x609);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr!(
+    /// Virtual Trap Interrupt control
+    , set_vti, clear_vti, 1 << 30);
+set_clear_csr!(
+    /// Default Priority Rank
+    , set_dpr, clear_dpr, 1 << 9);
+set_clear_csr!(
+    /// Interrupt Priority Mode
+    , set_ipriom, clear_ipriom, 1 << 8);
+
+/// Interrupt Identity of the virtual interrupt
+#[inline]
+pub unsafe fn set_iid(iid: u16) {
+    let mut value = _read();
+    value.set_bits(16..28, iid as usize & 0xfff);
+    _write(value);
+}
+
+/// Interrupt Priority of the virtual interrupt
+#[inline]
+pub unsafe fn set_iprio(iprio: u8) {
+    let mut value = _read();
+    value.set_bits(0..8, iprio as usize);
+    _write(value);
+}
@@ -0,0 +1,17 @@
+//! hviprio1 register
+//!
+//! Hypervisor Virtual Interrupt Priority register of the Advanced Interrupt Architecture (AIA).
+//! Each byte holds the priority of a virtual interrupt of VS-mode:
+//!
+//! | Bits  | Interrupt | Bits (RV64 only) | Interrupt |
+//! |-------|-----------|------------------|-----------|
+//! | 7:0   | 0         | 39:32            | 8         |
+//! | 15:8  | 1         | 47:40            | 13        |
+//! | 23:16 | 4         | 55:48            | 14        |
+//! | 31:24 | 5         | 63:56            | 15        |
+//!
+//! On RV32, the priorities of interrupts 8, 13, 14 and 15 are held by `hviprio1h`.
+//! Bits 7:0 (interrupt 0) are read-only zero.
+
+read_csr_as_usize!(0x646);
+write_csr_as_usize!(0x646);
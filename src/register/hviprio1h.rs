@@ -0,0 +1,16 @@
+//! hviprio1h register
+//!
+//! Hypervisor Virtual Interrupt Priority register of the Advanced Interrupt Architecture (AIA).
+//! Each byte holds the priority of a virtual interrupt of VS-mode:
+//!
+//! | Bits  | Interrupt |
+//! |-------|-----------|
+//! | 7:0   | 8         |
+//! | 15:8  | 13        |
+//! | 23:16 | 14        |
+//! | 31:24 | 15        |
+//!
+//! It is the upper half of `hviprio1` (RV32 only).
+
+read_csr_as_usize_rv32!(0x656);
+write_csr_as_usize_rv32!(0x656);
@@ -0,0 +1,16 @@
+//! hviprio2 register
+//!
+//! Hypervisor Virtual Interrupt Priority register of the Advanced Interrupt Architecture (AIA).
+//! Each byte holds the priority of a virtual interrupt of VS-mode:
+//!
+//! | Bits  | Interrupt | Bits (RV64 only) | Interrupt |
+//! |-------|-----------|------------------|-----------|
+//! | 7:0   | 16        | 39:32            | 20        |
+//! | 15:8  | 17        | 47:40            | 21        |
+//! | 23:16 | 18        | 55:48            | 22        |
+//! | 31:24 | 19        | 63:56            | 23        |
+//!
+//! On RV32, the priorities of interrupts 20 to 23 are held by `hviprio2h`.
+
+read_csr_as_usize!(0x647);
+write_csr_as_usize!(0x647);
@@ -0,0 +1,16 @@
+//! hviprio2h register
+//!
+//! Hypervisor Virtual Interrupt Priority register of the Advanced Interrupt Architecture (AIA).
+//! Each byte holds the priority of a virtual interrupt of VS-mode:
+//!
+//! | Bits  | Interrupt |
+//! |-------|-----------|
+//! | 7:0   | 20        |
+//! | 15:8  | 21        |
+//! | 23:16 | 22        |
+//! | 31:24 | 23        |
+//!
+//! It is the upper half of `hviprio2` (RV32 only).
+
+read_csr_as_usize_rv32!(0x657);
+write_csr_as_usize_rv32!(0x657);
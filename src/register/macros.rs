@@ -1,49 +1,97 @@
+/// Generates an unsafe private `_read` function that reads the CSR `$csr_number`.
+///
+/// On non-RISC-V targets, calling `_read` panics with `unimplemented!()`.
+///
+/// All the functions generated by the CSR macros are `#[inline(always)]`, so every
+/// CSR access compiles to a single CSR instruction with no call overhead.
+/// The `asm!` blocks are `nostack`, but not `nomem`: CSR accesses must not be
+/// reordered with memory accesses (e.g., when masking interrupts for a critical section).
+#[macro_export]
 macro_rules! read_csr {
     ($csr_number:literal) => {
         /// Reads the CSR
-        #[inline]
+        #[inline(always)]
         unsafe fn _read() -> usize {
             match () {
-                #[cfg(riscv)]
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
                 () => {
                     let r: usize;
-                    core::arch::asm!(concat!("csrrs {0}, ", stringify!($csr_number), ", x0"), out(reg) r);
+                    ::core::arch::asm!(concat!("csrrs {0}, ", stringify!($csr_number), ", x0"), out(reg) r, options(nostack));
                     r
                 }
 
-                #[cfg(not(riscv))]
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates an unsafe private `_read` function that reads the CSR `$csr_number` (RV32 only).
+///
+/// On targets other than RV32, calling `_read` panics with `unimplemented!()`.
+#[macro_export]
 macro_rules! read_csr_rv32 {
     ($csr_number:literal) => {
         /// Reads the CSR
-        #[inline]
+        #[inline(always)]
         unsafe fn _read() -> usize {
             match () {
-                #[cfg(riscv32)]
+                #[cfg(target_arch = "riscv32")]
                 () => {
                     let r: usize;
-                    core::arch::asm!(concat!("csrrs {0}, ", stringify!($csr_number), ", x0"), out(reg) r);
+                    ::core::arch::asm!(concat!("csrrs {0}, ", stringify!($csr_number), ", x0"), out(reg) r, options(nostack));
                     r
                 }
 
-                #[cfg(not(riscv32))]
+                #[cfg(not(target_arch = "riscv32"))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates a private `_read` function and a public `read` function that
+/// returns the CSR `$csr_number` wrapped in `$register`.
+///
+/// `$register` must be a struct defined in the calling module with a single `bits: usize` field.
+///
+/// # Example
+///
+/// ``` no_run
+/// mod mycsr {
+///     /// Vendor-specific CSR
+///     #[derive(Clone, Copy, Debug)]
+///     pub struct Mycsr {
+///         bits: usize,
+///     }
+///
+///     impl Mycsr {
+///         /// Returns the contents of the register as raw bits
+///         #[inline(always)]
+///         pub fn bits(&self) -> usize {
+///             self.bits
+///         }
+///     }
+///
+///     riscv::read_csr_as!(Mycsr, 0x7C0);
+///     riscv::write_csr!(0x7C0);
+///     riscv::set!(0x7C0);
+///     riscv::clear!(0x7C0);
+///     riscv::set_clear_csr!(
+///         /// Enable feature
+///         , set_feature, clear_feature, 1 << 0);
+/// }
+///
+/// let bits = mycsr::read().bits();
+/// ```
+#[macro_export]
 macro_rules! read_csr_as {
     ($register:ident, $csr_number:literal) => {
-        read_csr!($csr_number);
+        $crate::read_csr!($csr_number);
 
         /// Reads the CSR
-        #[inline]
+        #[inline(always)]
         pub fn read() -> $register {
             $register {
                 bits: unsafe { _read() },
@@ -52,156 +100,254 @@ macro_rules! read_csr_as {
     };
 }
 
+/// Generates a private `_read` function and a public `read` function that
+/// returns the CSR `$csr_number` as a `usize`.
+#[macro_export]
 macro_rules! read_csr_as_usize {
     ($csr_number:literal) => {
-        read_csr!($csr_number);
+        $crate::read_csr!($csr_number);
 
         /// Reads the CSR
-        #[inline]
+        #[inline(always)]
         pub fn read() -> usize {
             unsafe { _read() }
         }
     };
 }
 
+/// Generates a private `_read` function and a public `read` function that
+/// returns the CSR `$csr_number` as a `usize` (RV32 only).
+#[macro_export]
 macro_rules! read_csr_as_usize_rv32 {
     ($csr_number:literal) => {
-        read_csr_rv32!($csr_number);
+        $crate::read_csr_rv32!($csr_number);
 
         /// Reads the CSR
-        #[inline]
+        #[inline(always)]
         pub fn read() -> usize {
             unsafe { _read() }
         }
     };
 }
 
+/// Generates an unsafe private `_write` function that writes the CSR `$csr_number`.
+///
+/// On non-RISC-V targets, calling `_write` panics with `unimplemented!()`.
+#[macro_export]
 macro_rules! write_csr {
     ($csr_number:literal) => {
         /// Writes the CSR
-        #[inline]
+        #[inline(always)]
         #[allow(unused_variables)]
         unsafe fn _write(bits: usize) {
             match () {
-                #[cfg(riscv)]
-                () => core::arch::asm!(concat!("csrrw x0, ", stringify!($csr_number), ", {0}"), in(reg) bits),
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                () => ::core::arch::asm!(concat!("csrrw x0, ", stringify!($csr_number), ", {0}"), in(reg) bits, options(nostack)),
 
-                #[cfg(not(riscv))]
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates an unsafe private `_write` function that writes the CSR `$csr_number` (RV32 only).
+///
+/// On targets other than RV32, calling `_write` panics with `unimplemented!()`.
+#[macro_export]
 macro_rules! write_csr_rv32 {
     ($csr_number:literal) => {
         /// Writes the CSR
-        #[inline]
+        #[inline(always)]
         #[allow(unused_variables)]
         unsafe fn _write(bits: usize) {
             match () {
-                #[cfg(riscv32)]
-                () => core::arch::asm!(concat!("csrrw x0, ", stringify!($csr_number), ", {0}"), in(reg) bits),
+                #[cfg(target_arch = "riscv32")]
+                () => ::core::arch::asm!(concat!("csrrw x0, ", stringify!($csr_number), ", {0}"), in(reg) bits, options(nostack)),
 
-                #[cfg(not(riscv32))]
+                #[cfg(not(target_arch = "riscv32"))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates a private `_write` function and a public `write` function that
+/// writes a `usize` to the CSR `$csr_number`.
+#[macro_export]
 macro_rules! write_csr_as_usize {
     ($csr_number:literal) => {
-        write_csr!($csr_number);
+        $crate::write_csr!($csr_number);
 
         /// Writes the CSR
-        #[inline]
+        #[inline(always)]
         pub fn write(bits: usize) {
             unsafe { _write(bits) }
         }
     };
 }
 
+/// Generates a private `_write` function and a public `write` function that
+/// writes a `usize` to the CSR `$csr_number` (RV32 only).
+#[macro_export]
 macro_rules! write_csr_as_usize_rv32 {
     ($csr_number:literal) => {
-        write_csr_rv32!($csr_number);
+        $crate::write_csr_rv32!($csr_number);
 
         /// Writes the CSR
-        #[inline]
+        #[inline(always)]
         pub fn write(bits: usize) {
             unsafe { _write(bits) }
         }
     };
 }
 
+/// Generates an unsafe private `_set` function that sets bits of the CSR `$csr_number`
+/// with a single `csrrs` instruction.
+#[macro_export]
 macro_rules! set {
     ($csr_number:literal) => {
         /// Set the CSR
-        #[inline]
+        #[inline(always)]
         #[allow(unused_variables)]
         unsafe fn _set(bits: usize) {
             match () {
-                #[cfg(riscv)]
-                () => core::arch::asm!(concat!("csrrs x0, ", stringify!($csr_number), ", {0}"), in(reg) bits),
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                () => ::core::arch::asm!(concat!("csrrs x0, ", stringify!($csr_number), ", {0}"), in(reg) bits, options(nostack)),
 
-                #[cfg(not(riscv))]
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates an unsafe private `_clear` function that clears bits of the CSR `$csr_number`
+/// with a single `csrrc` instruction.
+#[macro_export]
 macro_rules! clear {
     ($csr_number:literal) => {
         /// Clear the CSR
-        #[inline]
+        #[inline(always)]
         #[allow(unused_variables)]
         unsafe fn _clear(bits: usize) {
             match () {
-                #[cfg(riscv)]
-                () => core::arch::asm!(concat!("csrrc x0, ", stringify!($csr_number), ", {0}"), in(reg) bits),
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                () => ::core::arch::asm!(concat!("csrrc x0, ", stringify!($csr_number), ", {0}"), in(reg) bits, options(nostack)),
 
-                #[cfg(not(riscv))]
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
                 () => unimplemented!(),
             }
         }
     };
 }
 
+/// Generates a public unsafe function `$set_field` that sets the bits `$e` of the CSR.
+///
+/// The calling module must invoke [`set!`](crate::set) first.
+#[macro_export]
 macro_rules! set_csr {
     ($(#[$attr:meta])*, $set_field:ident, $e:expr) => {
         $(#[$attr])*
-        #[inline]
+        #[inline(always)]
         pub unsafe fn $set_field() {
             _set($e);
         }
     };
 }
 
+/// Generates a public unsafe function `$clear_field` that clears the bits `$e` of the CSR.
+///
+/// The calling module must invoke [`clear!`](crate::clear) first.
+#[macro_export]
 macro_rules! clear_csr {
     ($(#[$attr:meta])*, $clear_field:ident, $e:expr) => {
         $(#[$attr])*
-        #[inline]
+        #[inline(always)]
         pub unsafe fn $clear_field() {
             _clear($e);
         }
     };
 }
 
+/// Generates both [`set_csr!`](crate::set_csr) and [`clear_csr!`](crate::clear_csr) functions
+/// for the bits `$e` of the CSR.
+#[macro_export]
 macro_rules! set_clear_csr {
     ($(#[$attr:meta])*, $set_field:ident, $clear_field:ident, $e:expr) => {
-        set_csr!($(#[$attr])*, $set_field, $e);
-        clear_csr!($(#[$attr])*, $clear_field, $e);
+        $crate::set_csr!($(#[$attr])*, $set_field, $e);
+        $crate::clear_csr!($(#[$attr])*, $clear_field, $e);
+    }
+}
+
+/// Generates a public unsafe function `$set_field` that sets the bits `$e` of the CSR `$csr_number`
+/// with a single `csrrsi` instruction.
+///
+/// `$e` must be a constant lower than 32, so it fits in the immediate field of the instruction.
+/// It saves the instruction that loads the mask into a register.
+#[macro_export]
+macro_rules! set_csr_imm {
+    ($(#[$attr:meta])*, $csr_number:literal, $set_field:ident, $e:expr) => {
+        $(#[$attr])*
+        #[inline(always)]
+        pub unsafe fn $set_field() {
+            const _: () = assert!($e < 32, "immediate CSR masks must be lower than 32");
+            match () {
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                () => ::core::arch::asm!(concat!("csrrsi x0, ", stringify!($csr_number), ", ", stringify!($e)), options(nostack)),
+
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+                () => unimplemented!(),
+            }
+        }
+    };
+}
+
+/// Generates a public unsafe function `$clear_field` that clears the bits `$e` of the CSR `$csr_number`
+/// with a single `csrrci` instruction.
+///
+/// `$e` must be a constant lower than 32, so it fits in the immediate field of the instruction.
+/// It saves the instruction that loads the mask into a register.
+#[macro_export]
+macro_rules! clear_csr_imm {
+    ($(#[$attr:meta])*, $csr_number:literal, $clear_field:ident, $e:expr) => {
+        $(#[$attr])*
+        #[inline(always)]
+        pub unsafe fn $clear_field() {
+            const _: () = assert!($e < 32, "immediate CSR masks must be lower than 32");
+            match () {
+                #[cfg(any(target_arch = "riscv32", target_arch = "riscv64"))]
+                () => ::core::arch::asm!(concat!("csrrci x0, ", stringify!($csr_number), ", ", stringify!($e)), options(nostack)),
+
+                #[cfg(not(any(target_arch = "riscv32", target_arch = "riscv64")))]
+                () => unimplemented!(),
+            }
+        }
+    };
+}
+
+/// Generates both [`set_csr_imm!`](crate::set_csr_imm) and [`clear_csr_imm!`](crate::clear_csr_imm)
+/// functions for the bits `$e` of the CSR `$csr_number`.
+#[macro_export]
+macro_rules! set_clear_csr_imm {
+    ($(#[$attr:meta])*, $csr_number:literal, $set_field:ident, $clear_field:ident, $e:expr) => {
+        $crate::set_csr_imm!($(#[$attr])*, $csr_number, $set_field, $e);
+        $crate::clear_csr_imm!($(#[$attr])*, $csr_number, $clear_field, $e);
     }
 }
 
+/// Generates a public `read64` function that reads a 64-bit counter.
+///
+/// On RV32, `$hi` and `$lo` are read in a loop until `$hi` is stable between both reads.
+/// On RV64, `$lo` already contains the full 64-bit value.
+#[macro_export]
 macro_rules! read_composite_csr {
     ($hi:expr, $lo:expr) => {
         /// Reads the CSR as a 64-bit value
-        #[inline]
+        #[inline(always)]
         pub fn read64() -> u64 {
             match () {
-                #[cfg(riscv32)]
+                #[cfg(target_arch = "riscv32")]
                 () => loop {
                     let hi = $hi;
                     let lo = $lo;
@@ -210,7 +356,7 @@ macro_rules! read_composite_csr {
                     }
                 },
 
-                #[cfg(not(riscv32))]
+                #[cfg(not(target_arch = "riscv32"))]
                 () => $lo as u64,
             }
         }
@@ -253,3 +399,114 @@ macro_rules! clear_pmp {
         }
     };
 }
+
+/// Generates a flags type `$flags` for interrupt enable/pending registers.
+///
+/// Each `$name = $bit` pair generates an associated constant of `$flags` with the bit `$bit` set.
+macro_rules! interrupt_flags {
+    ($(#[$attr:meta])* $flags:ident { $($(#[$fattr:meta])* $name:ident = $bit:literal,)* }) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+        pub struct $flags {
+            bits: usize,
+        }
+
+        impl $flags {
+            $(
+                $(#[$fattr])*
+                pub const $name: Self = Self { bits: 1 << $bit };
+            )*
+
+            /// Returns an empty set of flags
+            #[inline]
+            pub const fn empty() -> Self {
+                Self { bits: 0 }
+            }
+
+            /// Returns the set of all the standard flags
+            #[inline]
+            pub const fn all() -> Self {
+                Self {
+                    bits: 0 $(| (1 << $bit))*,
+                }
+            }
+
+            /// Creates a set of flags from raw bits
+            #[inline]
+            pub const fn from_bits(bits: usize) -> Self {
+                Self { bits }
+            }
+
+            /// Returns the set of flags as raw bits
+            #[inline]
+            pub const fn bits(&self) -> usize {
+                self.bits
+            }
+
+            /// Checks if the set of flags is empty
+            #[inline]
+            pub const fn is_empty(&self) -> bool {
+                self.bits == 0
+            }
+
+            /// Checks if all the flags of `other` are set
+            #[inline]
+            pub const fn contains(&self, other: Self) -> bool {
+                self.bits & other.bits == other.bits
+            }
+
+            /// Sets all the flags of `other`
+            #[inline]
+            pub fn insert(&mut self, other: Self) {
+                self.bits |= other.bits;
+            }
+
+            /// Clears all the flags of `other`
+            #[inline]
+            pub fn remove(&mut self, other: Self) {
+                self.bits &= !other.bits;
+            }
+        }
+
+        impl core::ops::BitOr for $flags {
+            type Output = Self;
+
+            #[inline]
+            fn bitor(self, rhs: Self) -> Self {
+                Self { bits: self.bits | rhs.bits }
+            }
+        }
+
+        impl core::ops::BitOrAssign for $flags {
+            #[inline]
+            fn bitor_assign(&mut self, rhs: Self) {
+                self.insert(rhs);
+            }
+        }
+
+        impl core::ops::BitAnd for $flags {
+            type Output = Self;
+
+            #[inline]
+            fn bitand(self, rhs: Self) -> Self {
+                Self { bits: self.bits & rhs.bits }
+            }
+        }
+
+        impl core::ops::BitAndAssign for $flags {
+            #[inline]
+            fn bitand_assign(&mut self, rhs: Self) {
+                self.bits &= rhs.bits;
+            }
+        }
+
+        impl core::ops::Not for $flags {
+            type Output = Self;
+
+            #[inline]
+            fn not(self) -> Self {
+                Self { bits: !self.bits }
+            }
+        }
+    };
+}
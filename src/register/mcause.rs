@@ -1,5 +1,8 @@
 //! mcause register
 
+use super::xlen::{self, Xlen};
+use core::fmt;
+
 /// mcause register
 #[derive(Clone, Copy, Debug)]
 pub struct Mcause {
@@ -45,67 +48,176 @@ pub enum Exception {
     InstructionPageFault,
     LoadPageFault,
     StorePageFault,
+    DoubleTrap,
     Unknown,
 }
 
 impl Interrupt {
+    /// Converts a cause code to its corresponding variant.
+    /// Unrecognized codes are converted to [`Interrupt::Unknown`].
     #[inline]
     pub fn from(nr: usize) -> Self {
+        Self::try_from(nr).unwrap_or(Interrupt::Unknown)
+    }
+
+    /// Returns the cause code of the interrupt.
+    /// It returns [`None`] for [`Interrupt::Unknown`].
+    #[inline]
+    pub const fn code(self) -> Option<usize> {
+        match self {
+            Interrupt::UserSoft => Some(0),
+            Interrupt::SupervisorSoft => Some(1),
+            Interrupt::MachineSoft => Some(3),
+            Interrupt::UserTimer => Some(4),
+            Interrupt::SupervisorTimer => Some(5),
+            Interrupt::MachineTimer => Some(7),
+            Interrupt::UserExternal => Some(8),
+            Interrupt::SupervisorExternal => Some(9),
+            Interrupt::MachineExternal => Some(11),
+            Interrupt::Unknown => None,
+        }
+    }
+}
+
+impl TryFrom<usize> for Interrupt {
+    type Error = usize;
+
+    /// Converts a cause code to its corresponding variant.
+    /// Unrecognized codes are returned back as an error.
+    #[inline]
+    fn try_from(nr: usize) -> Result<Self, Self::Error> {
         match nr {
-            0 => Interrupt::UserSoft,
-            1 => Interrupt::SupervisorSoft,
-            3 => Interrupt::MachineSoft,
-            4 => Interrupt::UserTimer,
-            5 => Interrupt::SupervisorTimer,
-            7 => Interrupt::MachineTimer,
-            8 => Interrupt::UserExternal,
-            9 => Interrupt::SupervisorExternal,
-            11 => Interrupt::MachineExternal,
-            _ => Interrupt::Unknown,
+            0 => Ok(Interrupt::UserSoft),
+            1 => Ok(Interrupt::SupervisorSoft),
+            3 => Ok(Interrupt::MachineSoft),
+            4 => Ok(Interrupt::UserTimer),
+            5 => Ok(Interrupt::SupervisorTimer),
+            7 => Ok(Interrupt::MachineTimer),
+            8 => Ok(Interrupt::UserExternal),
+            9 => Ok(Interrupt::SupervisorExternal),
+            11 => Ok(Interrupt::MachineExternal),
+            _ => Err(nr),
         }
     }
 }
 
 impl Exception {
+    /// Converts a cause code to its corresponding variant.
+    /// Unrecognized codes are converted to [`Exception::Unknown`].
     #[inline]
     pub fn from(nr: usize) -> Self {
+        Self::try_from(nr).unwrap_or(Exception::Unknown)
+    }
+
+    /// Returns the cause code of the exception.
+    /// It returns [`None`] for [`Exception::Unknown`].
+    #[inline]
+    pub const fn code(self) -> Option<usize> {
+        match self {
+            Exception::InstructionMisaligned => Some(0),
+            Exception::InstructionFault => Some(1),
+            Exception::IllegalInstruction => Some(2),
+            Exception::Breakpoint => Some(3),
+            Exception::LoadMisaligned => Some(4),
+            Exception::LoadFault => Some(5),
+            Exception::StoreMisaligned => Some(6),
+            Exception::StoreFault => Some(7),
+            Exception::UserEnvCall => Some(8),
+            Exception::SupervisorEnvCall => Some(9),
+            Exception::MachineEnvCall => Some(11),
+            Exception::InstructionPageFault => Some(12),
+            Exception::LoadPageFault => Some(13),
+            Exception::StorePageFault => Some(15),
+            Exception::DoubleTrap => Some(16),
+            Exception::Unknown => None,
+        }
+    }
+}
+
+impl Trap {
+    /// Encodes the trap cause with the `mcause` layout (i.e., with the interrupt bit for interrupts).
+    /// It returns [`None`] for unknown causes.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv::register::mcause::{Mcause, Exception, Interrupt, Trap};
+    ///
+    /// let trap = Trap::Interrupt(Interrupt::SupervisorTimer);
+    /// let mcause = Mcause::from_cause(trap).unwrap();
+    /// assert!(mcause.is_interrupt());
+    /// assert_eq!(mcause.code(), 5);
+    /// assert_eq!(mcause.cause(), trap);
+    ///
+    /// assert_eq!(Trap::Exception(Exception::Breakpoint).to_bits(), Some(3));
+    /// assert_eq!(Trap::Exception(Exception::Unknown).to_bits(), None);
+    /// ```
+    #[inline]
+    pub const fn to_bits(self) -> Option<usize> {
+        match self {
+            Trap::Interrupt(interrupt) => match interrupt.code() {
+                Some(code) => Some(code | 1 << (usize::BITS - 1)),
+                None => None,
+            },
+            Trap::Exception(exception) => exception.code(),
+        }
+    }
+}
+
+impl TryFrom<usize> for Exception {
+    type Error = usize;
+
+    /// Converts a cause code to its corresponding variant.
+    /// Unrecognized codes are returned back as an error.
+    #[inline]
+    fn try_from(nr: usize) -> Result<Self, Self::Error> {
         match nr {
-            0 => Exception::InstructionMisaligned,
-            1 => Exception::InstructionFault,
-            2 => Exception::IllegalInstruction,
-            3 => Exception::Breakpoint,
-            4 => Exception::LoadMisaligned,
-            5 => Exception::LoadFault,
-            6 => Exception::StoreMisaligned,
-            7 => Exception::StoreFault,
-            8 => Exception::UserEnvCall,
-            9 => Exception::SupervisorEnvCall,
-            11 => Exception::MachineEnvCall,
-            12 => Exception::InstructionPageFault,
-            13 => Exception::LoadPageFault,
-            15 => Exception::StorePageFault,
-            _ => Exception::Unknown,
+            0 => Ok(Exception::InstructionMisaligned),
+            1 => Ok(Exception::InstructionFault),
+            2 => Ok(Exception::IllegalInstruction),
+            3 => Ok(Exception::Breakpoint),
+            4 => Ok(Exception::LoadMisaligned),
+            5 => Ok(Exception::LoadFault),
+            6 => Ok(Exception::StoreMisaligned),
+            7 => Ok(Exception::StoreFault),
+            8 => Ok(Exception::UserEnvCall),
+            9 => Ok(Exception::SupervisorEnvCall),
+            11 => Ok(Exception::MachineEnvCall),
+            12 => Ok(Exception::InstructionPageFault),
+            13 => Ok(Exception::LoadPageFault),
+            15 => Ok(Exception::StorePageFault),
+            16 => Ok(Exception::DoubleTrap),
+            _ => Err(nr),
         }
     }
 }
+
 impl Mcause {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Creates a register value from a trap cause. It returns [`None`] for unknown causes.
+    #[inline]
+    pub const fn from_cause(cause: Trap) -> Option<Self> {
+        match cause.to_bits() {
+            Some(bits) => Some(Self { bits }),
+            None => None,
+        }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
-    pub fn bits(&self) -> usize {
+    pub const fn bits(&self) -> usize {
         self.bits
     }
 
     /// Returns the code field
     #[inline]
-    pub fn code(&self) -> usize {
-        match () {
-            #[cfg(target_pointer_width = "32")]
-            () => self.bits & !(1 << 31),
-            #[cfg(target_pointer_width = "64")]
-            () => self.bits & !(1 << 63),
-            #[cfg(target_pointer_width = "128")]
-            () => self.bits & !(1 << 127),
-        }
+    pub const fn code(&self) -> usize {
+        Xlen::NATIVE.clear_msb(xlen::to_u64(self.bits)) as usize
     }
 
     /// Trap Cause
@@ -120,22 +232,83 @@ impl Mcause {
 
     /// Is trap cause an interrupt.
     #[inline]
-    pub fn is_interrupt(&self) -> bool {
-        match () {
-            #[cfg(target_pointer_width = "32")]
-            () => self.bits & (1 << 31) == 1 << 31,
-            #[cfg(target_pointer_width = "64")]
-            () => self.bits & (1 << 63) == 1 << 63,
-            #[cfg(target_pointer_width = "128")]
-            () => self.bits & (1 << 127) == 1 << 127,
-        }
+    pub const fn is_interrupt(&self) -> bool {
+        Xlen::NATIVE.msb(xlen::to_u64(self.bits))
     }
 
     /// Is trap cause an exception.
     #[inline]
-    pub fn is_exception(&self) -> bool {
+    pub const fn is_exception(&self) -> bool {
         !self.is_interrupt()
     }
 }
 
+impl fmt::Display for Interrupt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Interrupt::UserSoft => "User software interrupt",
+            Interrupt::SupervisorSoft => "Supervisor software interrupt",
+            Interrupt::MachineSoft => "Machine software interrupt",
+            Interrupt::UserTimer => "User timer interrupt",
+            Interrupt::SupervisorTimer => "Supervisor timer interrupt",
+            Interrupt::MachineTimer => "Machine timer interrupt",
+            Interrupt::UserExternal => "User external interrupt",
+            Interrupt::SupervisorExternal => "Supervisor external interrupt",
+            Interrupt::MachineExternal => "Machine external interrupt",
+            Interrupt::Unknown => "Unknown interrupt",
+        })
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Exception::InstructionMisaligned => "Instruction address misaligned",
+            Exception::InstructionFault => "Instruction access fault",
+            Exception::IllegalInstruction => "Illegal instruction",
+            Exception::Breakpoint => "Breakpoint",
+            Exception::LoadMisaligned => "Load address misaligned",
+            Exception::LoadFault => "Load access fault",
+            Exception::StoreMisaligned => "Store/AMO address misaligned",
+            Exception::StoreFault => "Store/AMO access fault",
+            Exception::UserEnvCall => "Environment call from U-mode",
+            Exception::SupervisorEnvCall => "Environment call from S-mode",
+            Exception::MachineEnvCall => "Environment call from M-mode",
+            Exception::InstructionPageFault => "Instruction page fault",
+            Exception::LoadPageFault => "Load page fault",
+            Exception::StorePageFault => "Store/AMO page fault",
+            Exception::DoubleTrap => "Double trap",
+            Exception::Unknown => "Unknown exception",
+        })
+    }
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Trap::Interrupt(i) => i.fmt(f),
+            Trap::Exception(e) => e.fmt(f),
+        }
+    }
+}
+
+impl fmt::Display for Mcause {
+    /// Formats the trap cause. Unknown causes also include the code field.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.cause() {
+            Trap::Interrupt(Interrupt::Unknown) | Trap::Exception(Exception::Unknown) => {
+                write!(f, "{} (code {})", self.cause(), self.code())
+            }
+            cause => cause.fmt(f),
+        }
+    }
+}
+
 read_csr_as!(Mcause, 0x342);
+write_csr!(0x342);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
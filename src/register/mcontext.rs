@@ -0,0 +1,7 @@
+//! mcontext register
+//!
+//! Machine-mode context used by triggers to match a specific process or hart,
+//! and made available to external debuggers.
+
+read_csr_as_usize!(0x7A8);
+write_csr_as_usize!(0x7A8);
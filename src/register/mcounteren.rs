@@ -9,6 +9,18 @@ pub struct Mcounteren {
 }
 
 impl Mcounteren {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
     /// Supervisor "cycle\[h\]" Enable
     #[inline]
     pub fn cy(&self) -> bool {
@@ -40,17 +52,17 @@ write_csr!(0x306);
 set!(0x306);
 clear!(0x306);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// Supervisor cycle Enable
-    , set_cy, clear_cy, 1 << 0);
+    , 0x306, set_cy, clear_cy, 1 << 0);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// Supervisor time Enable
-    , set_tm, clear_tm, 1 << 1);
+    , 0x306, set_tm, clear_tm, 1 << 1);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// Supervisor instret Enable
-    , set_ir, clear_ir, 1 << 2);
+    , 0x306, set_ir, clear_ir, 1 << 2);
 
 #[inline]
 pub unsafe fn set_hpm(index: usize) {
@@ -9,6 +9,12 @@ pub struct Medeleg {
 }
 
 impl Medeleg {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -101,24 +107,31 @@ impl Medeleg {
 }
 
 read_csr_as!(Medeleg, 0x302);
+write_csr!(0x302);
 set!(0x302);
 clear!(0x302);
 
-set_clear_csr!(
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr_imm!(
     /// Instruction Address Misaligned Delegate
-    , set_instruction_misaligned, clear_instruction_misaligned, 1 << 0);
-set_clear_csr!(
+    , 0x302, set_instruction_misaligned, clear_instruction_misaligned, 1 << 0);
+set_clear_csr_imm!(
     /// Instruction Access Fault Delegate
-    , set_instruction_fault, clear_instruction_fault, 1 << 1);
-set_clear_csr!(
+    , 0x302, set_instruction_fault, clear_instruction_fault, 1 << 1);
+set_clear_csr_imm!(
     /// Illegal Instruction Delegate
-    , set_illegal_instruction, clear_illegal_instruction, 1 << 2);
-set_clear_csr!(
+    , 0x302, set_illegal_instruction, clear_illegal_instruction, 1 << 2);
+set_clear_csr_imm!(
     /// Breakpoint Delegate
-    , set_breakpoint, clear_breakpoint, 1 << 3);
-set_clear_csr!(
+    , 0x302, set_breakpoint, clear_breakpoint, 1 << 3);
+set_clear_csr_imm!(
     /// Load Address Misaligned Delegate
-    , set_load_misaligned, clear_load_misaligned, 1 << 4);
+    , 0x302, set_load_misaligned, clear_load_misaligned, 1 << 4);
 set_clear_csr!(
     /// Load Access Fault Delegate
     , set_load_fault, clear_load_fault, 1 << 5);
@@ -0,0 +1,226 @@
+//! menvcfg register
+//!
+//! Machine environment configuration register.
+//! It controls features of the execution environment of the modes below M-mode.
+//!
+//! The register is 64 bits wide. On RV32, the upper half is accessed through `menvcfgh` (0x31A).
+//! The functions of this module access both halves transparently.
+
+use bit_field::BitField;
+
+/// menvcfg register
+#[derive(Clone, Copy, Debug)]
+pub struct Menvcfg {
+    bits: u64,
+}
+
+/// Cache block invalidate instruction behavior
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CBIE {
+    /// `cbo.inval` raises an illegal instruction exception
+    IllegalInstruction = 0b00,
+    /// `cbo.inval` performs a flush
+    Flush = 0b01,
+    /// `cbo.inval` performs an invalidate
+    Invalidate = 0b11,
+}
+
+impl Menvcfg {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// Fence of I/O implies Memory
+    #[inline]
+    pub fn fiom(&self) -> bool {
+        self.bits.get_bit(0)
+    }
+
+    /// Cache Block Invalidate instruction Enable
+    #[inline]
+    pub fn cbie(&self) -> CBIE {
+        match self.bits.get_bits(4..6) {
+            0b01 => CBIE::Flush,
+            0b11 => CBIE::Invalidate,
+            _ => CBIE::IllegalInstruction,
+        }
+    }
+
+    /// Cache Block Clean and Flush instruction Enable
+    #[inline]
+    pub fn cbcfe(&self) -> bool {
+        self.bits.get_bit(6)
+    }
+
+    /// Cache Block Zero instruction Enable
+    #[inline]
+    pub fn cbze(&self) -> bool {
+        self.bits.get_bit(7)
+    }
+
+    /// Double Trap Enable (Ssdbltrp)
+    ///
+    /// When set, `sstatus.SDT` is enabled, and a trap taken in S-mode while it is set raises a double trap.
+    #[inline]
+    pub fn dte(&self) -> bool {
+        self.bits.get_bit(59)
+    }
+
+    /// Counter Delegation Enable (Smcdeleg)
+    ///
+    /// When set, S-mode can access the counters delegated by `mcounteren`
+    /// through the indirect CSRs and `scountinhibit`.
+    #[inline]
+    pub fn cde(&self) -> bool {
+        self.bits.get_bit(60)
+    }
+
+    /// Hardware A/D bit Update Enable (Svadu)
+    #[inline]
+    pub fn adue(&self) -> bool {
+        self.bits.get_bit(61)
+    }
+
+    /// Page-Based Memory Types Enable (Svpbmt)
+    #[inline]
+    pub fn pbmte(&self) -> bool {
+        self.bits.get_bit(62)
+    }
+
+    /// STimecmp Enable (Sstc)
+    #[inline]
+    pub fn stce(&self) -> bool {
+        self.bits.get_bit(63)
+    }
+}
+
+/// Reads the CSR
+#[inline]
+pub fn read() -> Menvcfg {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            let (lo, hi): (usize, usize);
+            unsafe {
+                core::arch::asm!("csrr {0}, 0x30A", "csrr {1}, 0x31A", out(reg) lo, out(reg) hi, options(nostack))
+            };
+            Menvcfg {
+                bits: ((hi as u64) << 32) | lo as u64,
+            }
+        }
+
+        #[cfg(riscv64)]
+        () => {
+            let bits: usize;
+            unsafe { core::arch::asm!("csrr {0}, 0x30A", out(reg) bits, options(nostack)) };
+            Menvcfg { bits: bits as u64 }
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Writes the CSR
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn write(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            core::arch::asm!("csrw 0x30A, {0}", "csrw 0x31A, {1}", in(reg) bits as usize, in(reg) (bits >> 32) as usize, options(nostack))
+        }
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrw 0x30A, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Sets bits of the CSR. On RV32, each half is only modified if it has bits to set
+#[inline]
+#[allow(unused_variables)]
+unsafe fn _set(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            if bits as u32 != 0 {
+                core::arch::asm!("csrs 0x30A, {0}", in(reg) bits as usize, options(nostack));
+            }
+            if (bits >> 32) != 0 {
+                core::arch::asm!("csrs 0x31A, {0}", in(reg) (bits >> 32) as usize, options(nostack));
+            }
+        }
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrs 0x30A, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Clears bits of the CSR. On RV32, each half is only modified if it has bits to clear
+#[inline]
+#[allow(unused_variables)]
+unsafe fn _clear(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            if bits as u32 != 0 {
+                core::arch::asm!("csrc 0x30A, {0}", in(reg) bits as usize, options(nostack));
+            }
+            if (bits >> 32) != 0 {
+                core::arch::asm!("csrc 0x31A, {0}", in(reg) (bits >> 32) as usize, options(nostack));
+            }
+        }
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrc 0x30A, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+set_clear_csr!(
+    /// Fence of I/O implies Memory
+    , set_fiom, clear_fiom, 1 << 0);
+set_clear_csr!(
+    /// Cache Block Clean and Flush instruction Enable
+    , set_cbcfe, clear_cbcfe, 1 << 6);
+set_clear_csr!(
+    /// Cache Block Zero instruction Enable
+    , set_cbze, clear_cbze, 1 << 7);
+set_clear_csr!(
+    /// Double Trap Enable (Ssdbltrp)
+    , set_dte, clear_dte, 1 << 59);
+set_clear_csr!(
+    /// Counter Delegation Enable (Smcdeleg)
+    , set_cde, clear_cde, 1 << 60);
+set_clear_csr!(
+    /// Hardware A/D bit Update Enable (Svadu)
+    , set_adue, clear_adue, 1 << 61);
+set_clear_csr!(
+    /// Page-Based Memory Types Enable (Svpbmt)
+    , set_pbmte, clear_pbmte, 1 << 62);
+set_clear_csr!(
+    /// STimecmp Enable (Sstc)
+    , set_stce, clear_stce, 1 << 63);
+
+/// Cache Block Invalidate instruction Enable
+#[inline]
+pub unsafe fn set_cbie(cbie: CBIE) {
+    _clear(0b11 << 4);
+    _set((cbie as u64) << 4);
+}
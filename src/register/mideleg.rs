@@ -9,6 +9,12 @@ pub struct Mideleg {
 }
 
 impl Mideleg {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -53,18 +59,25 @@ impl Mideleg {
 }
 
 read_csr_as!(Mideleg, 0x303);
+write_csr!(0x303);
 set!(0x303);
 clear!(0x303);
 
-set_clear_csr!(
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr_imm!(
     /// User Software Interrupt Delegate
-    , set_usoft, clear_usoft, 1 << 0);
-set_clear_csr!(
+    , 0x303, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Software Interrupt Delegate
-    , set_ssoft, clear_ssoft, 1 << 1);
-set_clear_csr!(
+    , 0x303, set_ssoft, clear_ssoft, 1 << 1);
+set_clear_csr_imm!(
     /// User Timer Interrupt Delegate
-    , set_utimer, clear_utimer, 1 << 4);
+    , 0x303, set_utimer, clear_utimer, 1 << 4);
 set_clear_csr!(
     /// Supervisor Timer Interrupt Delegate
     , set_stimer, clear_stimer, 1 << 5);
@@ -9,12 +9,24 @@ pub struct Mie {
 }
 
 impl Mie {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
         self.bits
     }
 
+    /// Returns the contents of the register as a set of flags
+    #[inline]
+    pub fn flags(&self) -> MieFlags {
+        MieFlags::from(*self)
+    }
+
     /// User Software Interrupt Enable
     #[inline]
     pub fn usoft(&self) -> bool {
@@ -68,24 +80,68 @@ impl Mie {
     pub fn mext(&self) -> bool {
         self.bits.get_bit(11)
     }
+
+    /// Platform-Specific Interrupt Enable
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not in the range `16..XLEN`.
+    #[inline]
+    pub fn custom(&self, n: usize) -> bool {
+        assert!((16..usize::BITS as usize).contains(&n));
+        self.bits.get_bit(n)
+    }
+}
+
+interrupt_flags! {
+    /// Set of mie flags
+    ///
+    /// Several flags can be combined with `|` and applied with a single CSR instruction.
+    MieFlags {
+        /// User Software Interrupt Enable
+        USOFT = 0,
+        /// Supervisor Software Interrupt Enable
+        SSOFT = 1,
+        /// Machine Software Interrupt Enable
+        MSOFT = 3,
+        /// User Timer Interrupt Enable
+        UTIMER = 4,
+        /// Supervisor Timer Interrupt Enable
+        STIMER = 5,
+        /// Machine Timer Interrupt Enable
+        MTIMER = 7,
+        /// User External Interrupt Enable
+        UEXT = 8,
+        /// Supervisor External Interrupt Enable
+        SEXT = 9,
+        /// Machine External Interrupt Enable
+        MEXT = 11,
+    }
+}
+
+impl From<Mie> for MieFlags {
+    #[inline]
+    fn from(value: Mie) -> Self {
+        Self::from_bits(value.bits)
+    }
 }
 
 read_csr_as!(Mie, 0x304);
 set!(0x304);
 clear!(0x304);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Software Interrupt Enable
-    , set_usoft, clear_usoft, 1 << 0);
-set_clear_csr!(
+    , 0x304, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Software Interrupt Enable
-    , set_ssoft, clear_ssoft, 1 << 1);
-set_clear_csr!(
+    , 0x304, set_ssoft, clear_ssoft, 1 << 1);
+set_clear_csr_imm!(
     /// Machine Software Interrupt Enable
-    , set_msoft, clear_msoft, 1 << 3);
-set_clear_csr!(
+    , 0x304, set_msoft, clear_msoft, 1 << 3);
+set_clear_csr_imm!(
     /// User Timer Interrupt Enable
-    , set_utimer, clear_utimer, 1 << 4);
+    , 0x304, set_utimer, clear_utimer, 1 << 4);
 set_clear_csr!(
     /// Supervisor Timer Interrupt Enable
     , set_stimer, clear_stimer, 1 << 5);
@@ -101,3 +157,41 @@ set_clear_csr!(
 set_clear_csr!(
     /// Machine External Interrupt Enable
     , set_mext, clear_mext, 1 << 11);
+
+/// Platform-Specific Interrupt Enable
+///
+/// Sets the enable bit of the platform-specific interrupt `n` with a single `csrrs` instruction.
+///
+/// # Panics
+///
+/// Panics if `n` is not in the range `16..XLEN`.
+#[inline]
+pub unsafe fn set_custom(n: usize) {
+    assert!((16..usize::BITS as usize).contains(&n));
+    _set(1 << n);
+}
+
+/// Platform-Specific Interrupt Enable
+///
+/// Clears the enable bit of the platform-specific interrupt `n` with a single `csrrc` instruction.
+///
+/// # Panics
+///
+/// Panics if `n` is not in the range `16..XLEN`.
+#[inline]
+pub unsafe fn clear_custom(n: usize) {
+    assert!((16..usize::BITS as usize).contains(&n));
+    _clear(1 << n);
+}
+
+/// Sets all the flags of `flags` with a single `csrrs` instruction
+#[inline]
+pub unsafe fn set_flags(flags: MieFlags) {
+    _set(flags.bits());
+}
+
+/// Clears all the flags of `flags` with a single `csrrc` instruction
+#[inline]
+pub unsafe fn clear_flags(flags: MieFlags) {
+    _clear(flags.bits());
+}
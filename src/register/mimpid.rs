@@ -9,6 +9,16 @@ pub struct Mimpid {
 }
 
 impl Mimpid {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump).
+    /// It returns [`None`] if `bits` is zero (i.e., the register is not implemented).
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Option<Self> {
+        match NonZeroUsize::new(bits) {
+            Some(bits) => Some(Self { bits }),
+            None => None,
+        }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
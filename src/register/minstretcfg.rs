@@ -0,0 +1,152 @@
+//! minstretcfg register
+//!
+//! Machine instructions-retired counter configuration register of the Smcntrpmf extension.
+//! Each inhibit bit stops `minstret` from counting while the hart runs in the corresponding privilege mode.
+//!
+//! The register is 64 bits wide. On RV32, the upper half is accessed through `minstretcfgh` (0x722).
+//! The functions of this module access both halves transparently.
+
+use bit_field::BitField;
+
+/// minstretcfg register
+#[derive(Clone, Copy, Debug)]
+pub struct Minstretcfg {
+    bits: u64,
+}
+
+impl Minstretcfg {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> u64 {
+        self.bits
+    }
+
+    /// M-mode inhibit
+    #[inline]
+    pub fn minh(&self) -> bool {
+        self.bits.get_bit(62)
+    }
+
+    /// S-mode inhibit
+    #[inline]
+    pub fn sinh(&self) -> bool {
+        self.bits.get_bit(61)
+    }
+
+    /// U-mode inhibit
+    #[inline]
+    pub fn uinh(&self) -> bool {
+        self.bits.get_bit(60)
+    }
+
+    /// VS-mode inhibit
+    #[inline]
+    pub fn vsinh(&self) -> bool {
+        self.bits.get_bit(59)
+    }
+
+    /// VU-mode inhibit
+    #[inline]
+    pub fn vuinh(&self) -> bool {
+        self.bits.get_bit(58)
+    }
+}
+
+/// Reads the CSR
+#[inline]
+pub fn read() -> Minstretcfg {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            let (lo, hi): (usize, usize);
+            unsafe {
+                core::arch::asm!("csrr {0}, 0x322", "csrr {1}, 0x722", out(reg) lo, out(reg) hi, options(nostack))
+            };
+            Minstretcfg {
+                bits: ((hi as u64) << 32) | lo as u64,
+            }
+        }
+
+        #[cfg(riscv64)]
+        () => {
+            let bits: usize;
+            unsafe { core::arch::asm!("csrr {0}, 0x322", out(reg) bits, options(nostack)) };
+            Minstretcfg { bits: bits as u64 }
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Writes the CSR
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn write(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => {
+            core::arch::asm!("csrw 0x322, {0}", "csrw 0x722, {1}", in(reg) bits as usize, in(reg) (bits >> 32) as usize, options(nostack))
+        }
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrw 0x322, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Sets bits of the CSR. On RV32, only bits of the upper half can be set
+#[inline]
+#[allow(unused_variables)]
+unsafe fn _set(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => core::arch::asm!("csrs 0x722, {0}", in(reg) (bits >> 32) as usize, options(nostack)),
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrs 0x322, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Clears bits of the CSR. On RV32, only bits of the upper half can be cleared
+#[inline]
+#[allow(unused_variables)]
+unsafe fn _clear(bits: u64) {
+    match () {
+        #[cfg(riscv32)]
+        () => core::arch::asm!("csrc 0x722, {0}", in(reg) (bits >> 32) as usize, options(nostack)),
+
+        #[cfg(riscv64)]
+        () => core::arch::asm!("csrc 0x322, {0}", in(reg) bits as usize, options(nostack)),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+set_clear_csr!(
+    /// M-mode inhibit
+    , set_minh, clear_minh, 1 << 62);
+set_clear_csr!(
+    /// S-mode inhibit
+    , set_sinh, clear_sinh, 1 << 61);
+set_clear_csr!(
+    /// U-mode inhibit
+    , set_uinh, clear_uinh, 1 << 60);
+set_clear_csr!(
+    /// VS-mode inhibit
+    , set_vsinh, clear_vsinh, 1 << 59);
+set_clear_csr!(
+    /// VU-mode inhibit
+    , set_vuinh, clear_vuinh, 1 << 58);
@@ -1,5 +1,6 @@
 //! mip register
 
+use super::scause::Interrupt;
 use bit_field::BitField;
 
 /// mip register
@@ -9,12 +10,24 @@ pub struct Mip {
 }
 
 impl Mip {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
         self.bits
     }
 
+    /// Returns the contents of the register as a set of flags
+    #[inline]
+    pub fn flags(&self) -> MipFlags {
+        MipFlags::from(*self)
+    }
+
     /// User Software Interrupt Pending
     #[inline]
     pub fn usoft(&self) -> bool {
@@ -68,21 +81,109 @@ impl Mip {
     pub fn mext(&self) -> bool {
         self.bits.get_bit(11)
     }
+
+    /// Platform-Specific Interrupt Pending
+    ///
+    /// Interrupt numbers 16 and above are reserved for platform use
+    /// (e.g., SiFive local interrupts).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is not in the range `16..XLEN`.
+    #[inline]
+    pub fn custom(&self, n: usize) -> bool {
+        assert!((16..usize::BITS as usize).contains(&n));
+        self.bits.get_bit(n)
+    }
+
+    /// Returns an iterator over the numbers of the pending platform-specific interrupts,
+    /// in increasing order.
+    #[inline]
+    pub fn custom_pending(&self) -> CustomPending {
+        CustomPending {
+            bits: self.bits & !0xffff,
+        }
+    }
+}
+
+/// Iterator over the numbers of pending platform-specific interrupts.
+///
+/// It is returned by [`Mip::custom_pending`].
+#[derive(Clone, Copy, Debug)]
+pub struct CustomPending {
+    bits: usize,
+}
+
+impl Iterator for CustomPending {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        match self.bits {
+            0 => None,
+            bits => {
+                let n = bits.trailing_zeros() as usize;
+                self.bits &= bits - 1;
+                Some(n)
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.bits.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl ExactSizeIterator for CustomPending {}
+
+interrupt_flags! {
+    /// Set of mip flags
+    ///
+    /// Several flags can be combined with `|` and applied with a single CSR instruction.
+    MipFlags {
+        /// User Software Interrupt Pending
+        USOFT = 0,
+        /// Supervisor Software Interrupt Pending
+        SSOFT = 1,
+        /// Machine Software Interrupt Pending
+        MSOFT = 3,
+        /// User Timer Interrupt Pending
+        UTIMER = 4,
+        /// Supervisor Timer Interrupt Pending
+        STIMER = 5,
+        /// Machine Timer Interrupt Pending
+        MTIMER = 7,
+        /// User External Interrupt Pending
+        UEXT = 8,
+        /// Supervisor External Interrupt Pending
+        SEXT = 9,
+        /// Machine External Interrupt Pending
+        MEXT = 11,
+    }
+}
+
+impl From<Mip> for MipFlags {
+    #[inline]
+    fn from(value: Mip) -> Self {
+        Self::from_bits(value.bits)
+    }
 }
 
 read_csr_as!(Mip, 0x344);
 set!(0x344);
 clear!(0x344);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Software Interrupt Pending
-    , set_usoft, clear_usoft, 1 << 0);
-set_clear_csr!(
+    , 0x344, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Software Interrupt Pending
-    , set_ssoft, clear_ssoft, 1 << 1);
-set_clear_csr!(
+    , 0x344, set_ssoft, clear_ssoft, 1 << 1);
+set_clear_csr_imm!(
     /// User Timer Interrupt Pending
-    , set_utimer, clear_utimer, 1 << 4);
+    , 0x344, set_utimer, clear_utimer, 1 << 4);
 set_clear_csr!(
     /// Supervisor Timer Interrupt Pending
     , set_stimer, clear_stimer, 1 << 5);
@@ -92,3 +193,51 @@ set_clear_csr!(
 set_clear_csr!(
     /// Supervisor External Interrupt Pending
     , set_sext, clear_sext, 1 << 9);
+
+/// Injects a supervisor or user interrupt by setting its pending bit with a single `csrrs` instruction.
+///
+/// This is how M-mode firmware (e.g., an SBI implementation) forwards timer and software
+/// interrupts to S-mode. The interrupt is taken once it is enabled in `sie` and delegated
+/// in `mideleg`. [`Interrupt::Unknown`] is ignored.
+///
+/// # Example
+///
+/// ``` no_run
+/// use riscv::register::{mip, scause::Interrupt};
+///
+/// // machine timer interrupt handler: forward the timer event to S-mode
+/// unsafe { mip::inject(Interrupt::SupervisorTimer) };
+/// ```
+#[inline]
+pub unsafe fn inject(interrupt: Interrupt) {
+    if let Some(code) = interrupt.code() {
+        _set(1 << code);
+    }
+}
+
+/// Retracts a supervisor or user interrupt by clearing its pending bit with a single `csrrc` instruction.
+///
+/// S-mode usually acknowledges the supervisor timer interrupt through the SBI, which then calls this function.
+/// [`Interrupt::Unknown`] is ignored.
+#[inline]
+pub unsafe fn retract(interrupt: Interrupt) {
+    if let Some(code) = interrupt.code() {
+        _clear(1 << code);
+    }
+}
+
+/// Sets all the flags of `flags` with a single `csrrs` instruction
+///
+/// Read-only bits (e.g., `MEXT`) are not modified.
+#[inline]
+pub unsafe fn set_flags(flags: MipFlags) {
+    _set(flags.bits());
+}
+
+/// Clears all the flags of `flags` with a single `csrrc` instruction
+///
+/// Read-only bits (e.g., `MEXT`) are not modified.
+#[inline]
+pub unsafe fn clear_flags(flags: MipFlags) {
+    _clear(flags.bits());
+}
@@ -1,5 +1,6 @@
 //! misa register
 
+use super::xlen::{self, Xlen};
 use core::num::NonZeroUsize;
 
 /// misa register
@@ -17,6 +18,16 @@ pub enum MXL {
 }
 
 impl Misa {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump).
+    /// It returns [`None`] if `bits` is zero (i.e., the register is not implemented).
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Option<Self> {
+        match NonZeroUsize::new(bits) {
+            Some(bits) => Some(Self { bits }),
+            None => None,
+        }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -26,12 +37,7 @@ impl Misa {
     /// Returns the machine xlen.
     #[inline]
     pub fn mxl(&self) -> MXL {
-        let value = match () {
-            #[cfg(target_pointer_width = "32")]
-            () => (self.bits() >> 30) as u8,
-            #[cfg(target_pointer_width = "64")]
-            () => (self.bits() >> 62) as u8,
-        };
+        let value = Xlen::NATIVE.top_bits(xlen::to_u64(self.bits()), 2);
         match value {
             1 => MXL::XLEN32,
             2 => MXL::XLEN64,
@@ -9,6 +9,8 @@
 //! - mcycleh
 //! - minstreth
 //! - mhpmcounter<3-31>h
+//! - hviprio1h
+//! - hviprio2h
 
 #[macro_use]
 mod macros;
@@ -29,6 +31,9 @@ pub mod utval;
 // TODO: frm, fflags
 pub mod fcsr;
 
+// User Entropy Source (Zkr)
+pub mod seed;
+
 // User Counter/Timers
 pub mod cycle;
 pub mod cycleh;
@@ -42,6 +47,7 @@ pub mod timeh;
 // Supervisor Trap Setup
 // TODO: sedeleg, sideleg
 pub mod scounteren;
+pub mod scountinhibit;
 pub mod sie;
 pub mod sstatus;
 pub mod stvec;
@@ -56,6 +62,23 @@ pub mod stval;
 // Supervisor Protection and Translation
 pub mod satp;
 
+// Hypervisor Trap Setup
+pub mod hstatus;
+
+// Virtual Supervisor Registers
+pub mod vscause;
+pub mod vsepc;
+pub mod vsstatus;
+pub mod vstval;
+pub mod vstvec;
+
+// Hypervisor Virtual Interrupts (AIA)
+pub mod hvictl;
+pub mod hviprio1;
+pub mod hviprio1h;
+pub mod hviprio2;
+pub mod hviprio2h;
+
 // Machine Information Registers
 pub mod marchid;
 pub mod mhartid;
@@ -65,6 +88,7 @@ pub mod mvendorid;
 // Machine Trap Setup
 pub mod mcounteren;
 pub mod medeleg;
+pub mod menvcfg;
 pub mod mideleg;
 pub mod mie;
 pub mod misa;
@@ -93,9 +117,26 @@ pub mod minstret;
 pub mod minstreth;
 
 // Machine Counter Setup
+pub mod mcyclecfg;
 mod mhpmeventx;
 pub use self::mhpmeventx::*;
-
-// TODO: Debug/Trace Registers (shared with Debug Mode)
-
-// TODO: Debug Mode Registers
+pub mod minstretcfg;
+
+// Debug/Trace Registers (shared with Debug Mode)
+pub mod mcontext;
+pub mod scontext;
+pub mod tdata1;
+pub mod tdata2;
+pub mod tdata3;
+pub mod tinfo;
+pub mod tselect;
+
+// Debug Mode Registers
+pub mod dcsr;
+
+// XLEN-generic register values
+pub mod xlen;
+
+// Vendor-specific CSRs
+pub mod sifive;
+pub mod vexriscv;
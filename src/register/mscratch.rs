@@ -1,4 +1,59 @@
 //! mscratch register
+//!
+//! `mscratch` is typically used to hold the top of a dedicated trap stack.
+//! On trap entry, the handler swaps `sp` and `mscratch` with a single `csrrw`
+//! instruction, so it runs on the trap stack while `mscratch` keeps the interrupted `sp`.
+//! Right before `mret`, the same instruction swaps them back.
+//! See [`crate::swap_sp_mscratch`] for a ready-to-use assembly snippet.
 
 read_csr_as_usize!(0x340);
 write_csr_as_usize!(0x340);
+
+/// Atomically swaps the CSR with `bits` and returns its previous value
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn swap(bits: usize) -> usize {
+    match () {
+        #[cfg(riscv)]
+        () => {
+            let r: usize;
+            core::arch::asm!("csrrw {0}, mscratch, {1}", out(reg) r, in(reg) bits);
+            r
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Initializes the CSR with the top of the trap stack.
+///
+/// `stack_top` must point one past the highest address of the trap stack,
+/// and it must be 16-byte aligned as required by the RISC-V calling convention.
+///
+/// # Safety
+///
+/// The trap stack must be large enough for the trap handler and must not be used for anything else.
+/// Traps must not be nested while `sp` and `mscratch` are swapped, as a nested trap would swap them again.
+///
+/// # Example
+///
+/// ``` no_run
+/// const TRAP_STACK_SIZE: usize = 4096;
+///
+/// #[repr(C, align(16))]
+/// struct TrapStack([u8; TRAP_STACK_SIZE]);
+///
+/// static mut TRAP_STACK: TrapStack = TrapStack([0; TRAP_STACK_SIZE]);
+///
+/// // the trap entry swaps `sp` and `mscratch` with `riscv::swap_sp_mscratch!()`
+/// unsafe {
+///     let stack_top = core::ptr::addr_of_mut!(TRAP_STACK).cast::<u8>().add(TRAP_STACK_SIZE);
+///     riscv::register::mscratch::init_trap_stack(stack_top);
+/// }
+/// ```
+#[inline]
+pub unsafe fn init_trap_stack(stack_top: *mut u8) {
+    debug_assert_eq!(stack_top as usize % 16, 0, "misaligned trap stack");
+    write(stack_top as usize);
+}
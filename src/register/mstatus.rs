@@ -1,4 +1,16 @@
 //! mstatus register
+//!
+//! # Example
+//!
+//! ```
+//! use riscv::register::mstatus::{Mstatus, MPP, VS};
+//!
+//! // mstatus captured in a trap handler: M-mode trap taken from M-mode with interrupts enabled
+//! let mstatus = Mstatus::from_bits(0x1880);
+//! assert_eq!(mstatus.mpp(), MPP::Machine);
+//! assert!(mstatus.mpie() && !mstatus.mie());
+//! assert_eq!(mstatus.vs(), VS::Off);
+//! ```
 
 // FIXME: in 1.12 spec there will be `SBE` and `MBE` bits.
 // They allows to execute supervisor in given big endian,
@@ -7,6 +19,7 @@
 // FIXME: `SXL` and `UXL` bits require a structure interpreting XLEN,
 // which would be the best way we implement this using Rust?
 
+use super::xlen::{self, Xlen};
 use bit_field::BitField;
 
 /// mstatus register
@@ -40,6 +53,15 @@ pub enum FS {
     Dirty = 3,
 }
 
+/// Vector extension state
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum VS {
+    Off = 0,
+    Initial = 1,
+    Clean = 2,
+    Dirty = 3,
+}
+
 /// Machine Previous Privilege Mode
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum MPP {
@@ -56,55 +78,82 @@ pub enum SPP {
 }
 
 impl Mstatus {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub const fn bits(&self) -> usize {
+        self.bits
+    }
+
     /// User Interrupt Enable
     #[inline]
-    pub fn uie(&self) -> bool {
-        self.bits.get_bit(0)
+    pub const fn uie(&self) -> bool {
+        self.bits & (1 << 0) != 0
     }
 
     /// Supervisor Interrupt Enable
     #[inline]
-    pub fn sie(&self) -> bool {
-        self.bits.get_bit(1)
+    pub const fn sie(&self) -> bool {
+        self.bits & (1 << 1) != 0
     }
 
     /// Machine Interrupt Enable
     #[inline]
-    pub fn mie(&self) -> bool {
-        self.bits.get_bit(3)
+    pub const fn mie(&self) -> bool {
+        self.bits & (1 << 3) != 0
     }
 
     /// User Previous Interrupt Enable
     #[inline]
-    pub fn upie(&self) -> bool {
-        self.bits.get_bit(4)
+    pub const fn upie(&self) -> bool {
+        self.bits & (1 << 4) != 0
     }
 
     /// Supervisor Previous Interrupt Enable
     #[inline]
-    pub fn spie(&self) -> bool {
-        self.bits.get_bit(5)
+    pub const fn spie(&self) -> bool {
+        self.bits & (1 << 5) != 0
     }
 
     /// Machine Previous Interrupt Enable
     #[inline]
-    pub fn mpie(&self) -> bool {
-        self.bits.get_bit(7)
+    pub const fn mpie(&self) -> bool {
+        self.bits & (1 << 7) != 0
     }
 
     /// Supervisor Previous Privilege Mode
     #[inline]
-    pub fn spp(&self) -> SPP {
-        match self.bits.get_bit(8) {
+    pub const fn spp(&self) -> SPP {
+        match self.bits & (1 << 8) != 0 {
             true => SPP::Supervisor,
             false => SPP::User,
         }
     }
 
+    /// Vector extension state
+    ///
+    /// Encodes the status of the vector unit, including the vector registers
+    /// and the CSRs `vcsr`, `vstart`, `vl`, `vtype` and `vlenb`.
+    #[inline]
+    pub const fn vs(&self) -> VS {
+        match (self.bits >> 9) & 0b11 {
+            0b00 => VS::Off,
+            0b01 => VS::Initial,
+            0b10 => VS::Clean,
+            0b11 => VS::Dirty,
+            _ => unreachable!(),
+        }
+    }
+
     /// Machine Previous Privilege Mode
     #[inline]
-    pub fn mpp(&self) -> MPP {
-        match self.bits.get_bits(11..13) {
+    pub const fn mpp(&self) -> MPP {
+        match (self.bits >> 11) & 0b11 {
             0b00 => MPP::User,
             0b01 => MPP::Supervisor,
             0b11 => MPP::Machine,
@@ -117,8 +166,8 @@ impl Mstatus {
     /// Encodes the status of the floating-point unit,
     /// including the CSR `fcsr` and floating-point data registers `f0–f31`.
     #[inline]
-    pub fn fs(&self) -> FS {
-        match self.bits.get_bits(13..15) {
+    pub const fn fs(&self) -> FS {
+        match (self.bits >> 13) & 0b11 {
             0b00 => FS::Off,
             0b01 => FS::Initial,
             0b10 => FS::Clean,
@@ -130,9 +179,10 @@ impl Mstatus {
     /// Additional extension state
     ///
     /// Encodes the status of additional user-mode extensions and associated state.
+    /// This field is read-only, as it summarizes the state of each extension.
     #[inline]
-    pub fn xs(&self) -> XS {
-        match self.bits.get_bits(15..17) {
+    pub const fn xs(&self) -> XS {
+        match (self.bits >> 15) & 0b11 {
             0b00 => XS::AllOff,
             0b01 => XS::NoneDirtyOrClean,
             0b10 => XS::NoneDirtySomeClean,
@@ -143,20 +193,20 @@ impl Mstatus {
 
     /// Modify Memory PRiVilege
     #[inline]
-    pub fn mprv(&self) -> bool {
-        self.bits.get_bit(17)
+    pub const fn mprv(&self) -> bool {
+        self.bits & (1 << 17) != 0
     }
 
     /// Permit Supervisor User Memory access
     #[inline]
-    pub fn sum(&self) -> bool {
-        self.bits.get_bit(18)
+    pub const fn sum(&self) -> bool {
+        self.bits & (1 << 18) != 0
     }
 
     /// Make eXecutable Readable
     #[inline]
-    pub fn mxr(&self) -> bool {
-        self.bits.get_bit(19)
+    pub const fn mxr(&self) -> bool {
+        self.bits & (1 << 19) != 0
     }
 
     /// Trap Virtual Memory
@@ -166,8 +216,8 @@ impl Mstatus {
     ///
     /// TVM is hard-wired to 0 when S-mode is not supported.
     #[inline]
-    pub fn tvm(&self) -> bool {
-        self.bits.get_bit(20)
+    pub const fn tvm(&self) -> bool {
+        self.bits & (1 << 20) != 0
     }
 
     /// Timeout Wait
@@ -180,8 +230,8 @@ impl Mstatus {
     ///
     /// TW is hard-wired to 0 when S-mode is not supported.
     #[inline]
-    pub fn tw(&self) -> bool {
-        self.bits.get_bit(21)
+    pub const fn tw(&self) -> bool {
+        self.bits & (1 << 21) != 0
     }
 
     /// Trap SRET
@@ -191,8 +241,18 @@ impl Mstatus {
     ///
     /// If S-mode is not supported, TSR bit is hard-wired to 0.
     #[inline]
-    pub fn tsr(&self) -> bool {
-        self.bits.get_bit(22)
+    pub const fn tsr(&self) -> bool {
+        self.bits & (1 << 22) != 0
+    }
+
+    /// Supervisor Disable Trap (Ssdbltrp)
+    ///
+    /// Set by hardware when a trap is taken into S-mode. If a trap is taken into S-mode
+    /// while this bit is set, a double trap exception is raised in M-mode instead.
+    /// It is read-only zero unless `menvcfg.DTE` is set.
+    #[inline]
+    pub const fn sdt(&self) -> bool {
+        self.bits & (1 << 24) != 0
     }
 
     /*
@@ -203,8 +263,8 @@ impl Mstatus {
     /// Whether either the FS field or XS field
     /// signals the presence of some dirty state
     #[inline]
-    pub fn sd(&self) -> bool {
-        self.bits.get_bit(usize::BITS as usize - 1)
+    pub const fn sd(&self) -> bool {
+        Xlen::NATIVE.msb(xlen::to_u64(self.bits))
     }
 }
 
@@ -213,24 +273,24 @@ write_csr!(0x300);
 set!(0x300);
 clear!(0x300);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Interrupt Enable
-    , set_uie, clear_uie, 1 << 0);
-set_clear_csr!(
+    , 0x300, set_uie, clear_uie, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Interrupt Enable
-    , set_sie, clear_sie, 1 << 1);
-set_clear_csr!(
+    , 0x300, set_sie, clear_sie, 1 << 1);
+set_clear_csr_imm!(
     /// Machine Interrupt Enable
-    , set_mie, clear_mie, 1 << 3);
-set_csr!(
+    , 0x300, set_mie, clear_mie, 1 << 3);
+set_csr_imm!(
     /// User Previous Interrupt Enable
-    , set_upie, 1 << 4);
-set_csr!(
+    , 0x300, set_upie, 1 << 4);
+set_clear_csr!(
     /// Supervisor Previous Interrupt Enable
-    , set_spie, 1 << 5);
-set_csr!(
+    , set_spie, clear_spie, 1 << 5);
+set_clear_csr!(
     /// Machine Previous Interrupt Enable
-    , set_mpie, 1 << 7);
+    , set_mpie, clear_mpie, 1 << 7);
 set_clear_csr!(
     /// Modify Memory PRiVilege
     , set_mprv, clear_mprv, 1 << 17);
@@ -249,6 +309,9 @@ set_clear_csr!(
 set_clear_csr!(
     /// Trap SRET
     , set_tsr, clear_tsr, 1 << 22);
+set_clear_csr!(
+    /// Supervisor Disable Trap (Ssdbltrp)
+    , set_sdt, clear_sdt, 1 << 24);
 
 /// Supervisor Previous Privilege Mode
 #[inline]
@@ -274,3 +337,11 @@ pub unsafe fn set_fs(fs: FS) {
     value.set_bits(13..15, fs as usize);
     _write(value);
 }
+
+/// Vector extension state
+#[inline]
+pub unsafe fn set_vs(vs: VS) {
+    let mut value = _read();
+    value.set_bits(9..11, vs as usize);
+    _write(value);
+}
@@ -7,34 +7,75 @@ pub struct Mtvec {
 }
 
 /// Trap mode
+///
+/// # Note
+///
+/// This enum is non-exhaustive, as its variants depend on the enabled features.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum TrapMode {
     Direct = 0,
     Vectored = 1,
+    /// Ratified CLIC mode (`xtvec[5:0] = 000011`)
+    ///
+    /// # Note
+    ///
+    /// This variant requires the `clic` feature.
+    /// In CLIC mode, the trap-vector base-address must be 64-byte aligned.
+    #[cfg(feature = "clic")]
+    Clic = 0b00_0011,
+}
+
+impl TrapMode {
+    /// Decodes the trap mode of the raw value of a trap-vector register
+    #[inline]
+    pub(crate) const fn from_tvec(bits: usize) -> Option<Self> {
+        match bits & 0b11 {
+            0 => Some(Self::Direct),
+            1 => Some(Self::Vectored),
+            #[cfg(feature = "clic")]
+            0b11 if bits & 0b11_1111 == Self::Clic as usize => Some(Self::Clic),
+            _ => None,
+        }
+    }
+
+    /// Returns the mask of the mode bits of a trap-vector register in this mode
+    #[inline]
+    pub(crate) const fn mask(self) -> usize {
+        match self {
+            #[cfg(feature = "clic")]
+            Self::Clic => 0b11_1111,
+            _ => 0b11,
+        }
+    }
 }
 
 impl Mtvec {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
-    pub fn bits(&self) -> usize {
+    pub const fn bits(&self) -> usize {
         self.bits
     }
 
     /// Returns the trap-vector base-address
     #[inline]
-    pub fn address(&self) -> usize {
-        self.bits - (self.bits & 0b11)
+    pub const fn address(&self) -> usize {
+        match self.trap_mode() {
+            Some(mode) => self.bits & !mode.mask(),
+            None => self.bits & !0b11,
+        }
     }
 
     /// Returns the trap-vector mode
     #[inline]
-    pub fn trap_mode(&self) -> Option<TrapMode> {
-        let mode = self.bits & 0b11;
-        match mode {
-            0 => Some(TrapMode::Direct),
-            1 => Some(TrapMode::Vectored),
-            _ => None,
-        }
+    pub const fn trap_mode(&self) -> Option<TrapMode> {
+        TrapMode::from_tvec(self.bits)
     }
 }
 
@@ -45,6 +86,7 @@ write_csr!(0x305);
 /// Writes the CSR
 #[inline]
 pub unsafe fn write(addr: usize, mode: TrapMode) {
+    debug_assert_eq!(addr & mode.mask(), 0, "misaligned trap-vector base-address");
     let bits = addr + mode as usize;
     _write(bits);
 }
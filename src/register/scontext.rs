@@ -0,0 +1,7 @@
+//! scontext register
+//!
+//! Supervisor-mode context used by triggers to match a specific process (e.g., its PID),
+//! and made available to external debuggers.
+
+read_csr_as_usize!(0x5A8);
+write_csr_as_usize!(0x5A8);
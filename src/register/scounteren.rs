@@ -9,6 +9,12 @@ pub struct Scounteren {
 }
 
 impl Scounteren {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// User "cycle\[h\]" Enable
     #[inline]
     pub fn cy(&self) -> bool {
@@ -40,17 +46,17 @@ write_csr!(0x106);
 set!(0x106);
 clear!(0x106);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// User cycle Enable
-    , set_cy, clear_cy, 1 << 0);
+    , 0x106, set_cy, clear_cy, 1 << 0);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// User time Enable
-    , set_tm, clear_tm, 1 << 1);
+    , 0x106, set_tm, clear_tm, 1 << 1);
 
-set_clear_csr!(
+set_clear_csr_imm!(
 /// User instret Enable
-    , set_ir, clear_ir, 1 << 2);
+    , 0x106, set_ir, clear_ir, 1 << 2);
 
 #[inline]
 pub unsafe fn set_hpm(index: usize) {
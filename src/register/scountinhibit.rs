@@ -0,0 +1,78 @@
+//! scountinhibit register
+//!
+//! Supervisor counter-inhibit register of the Ssccfg extension.
+//! It is an S-mode alias of the bits of `mcountinhibit` delegated with `menvcfg.CDE`.
+//! Setting a bit stops the corresponding counter.
+
+use bit_field::BitField;
+
+/// scountinhibit register
+#[derive(Clone, Copy, Debug)]
+pub struct Scountinhibit {
+    bits: usize,
+}
+
+impl Scountinhibit {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// "cycle\[h\]" Inhibit
+    #[inline]
+    pub fn cy(&self) -> bool {
+        self.bits.get_bit(0)
+    }
+
+    /// "instret\[h\]" Inhibit
+    #[inline]
+    pub fn ir(&self) -> bool {
+        self.bits.get_bit(2)
+    }
+
+    /// "hpm\[x\]" Inhibit (bits 3-31)
+    #[inline]
+    pub fn hpm(&self, index: usize) -> bool {
+        assert!((3..32).contains(&index));
+        self.bits.get_bit(index)
+    }
+}
+
+read_csr_as!(Scountinhibit, 0x120);
+write_csr!(0x120);
+set!(0x120);
+clear!(0x120);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr_imm!(
+    /// "cycle\[h\]" Inhibit
+    , 0x120, set_cy, clear_cy, 1 << 0);
+set_clear_csr_imm!(
+    /// "instret\[h\]" Inhibit
+    , 0x120, set_ir, clear_ir, 1 << 2);
+
+/// "hpm\[x\]" Inhibit (bits 3-31)
+#[inline]
+pub unsafe fn set_hpm(index: usize) {
+    assert!((3..32).contains(&index));
+    _set(1 << index);
+}
+
+/// "hpm\[x\]" Inhibit (bits 3-31)
+#[inline]
+pub unsafe fn clear_hpm(index: usize) {
+    assert!((3..32).contains(&index));
+    _clear(1 << index);
+}
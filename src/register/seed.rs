@@ -0,0 +1,71 @@
+//! seed register
+//!
+//! Entropy source register of the Zkr extension.
+//! Every access polls the entropy source, so it must be accessed with a read-write instruction
+//! (reading it with `csrrs` raises an illegal instruction exception).
+
+/// seed register
+#[derive(Clone, Copy, Debug)]
+pub struct Seed {
+    bits: usize,
+}
+
+/// Operational status of the entropy source
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum OPST {
+    /// Built-in self-test in progress. No entropy is available yet
+    Bist = 0b00,
+    /// No entropy is available right now. Poll again later
+    Wait = 0b01,
+    /// 16 bits of entropy are available in the `entropy` field
+    Es16 = 0b10,
+    /// Unrecoverable self-test failure. The entropy source will not produce entropy again
+    Dead = 0b11,
+}
+
+impl Seed {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub const fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Operational status
+    #[inline]
+    pub const fn opst(&self) -> OPST {
+        match (self.bits >> 30) & 0b11 {
+            0b00 => OPST::Bist,
+            0b01 => OPST::Wait,
+            0b10 => OPST::Es16,
+            _ => OPST::Dead,
+        }
+    }
+
+    /// Entropy bits. They are only valid if [`Seed::opst`] is [`OPST::Es16`]
+    #[inline]
+    pub const fn entropy(&self) -> u16 {
+        self.bits as u16
+    }
+}
+
+/// Polls the entropy source with a `csrrw` instruction
+#[inline]
+pub fn read() -> Seed {
+    match () {
+        #[cfg(riscv)]
+        () => {
+            let bits: usize;
+            unsafe { core::arch::asm!("csrrw {0}, 0x015, x0", out(reg) bits, options(nostack)) };
+            Seed { bits }
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
@@ -9,6 +9,12 @@ pub struct Sie {
 }
 
 impl Sie {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -56,15 +62,15 @@ read_csr_as!(Sie, 0x104);
 set!(0x104);
 clear!(0x104);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Software Interrupt Enable
-    , set_usoft, clear_usoft, 1 << 0);
-set_clear_csr!(
+    , 0x104, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Software Interrupt Enable
-    , set_ssoft, clear_ssoft, 1 << 1);
-set_clear_csr!(
+    , 0x104, set_ssoft, clear_ssoft, 1 << 1);
+set_clear_csr_imm!(
     /// User Timer Interrupt Enable
-    , set_utimer, clear_utimer, 1 << 4);
+    , 0x104, set_utimer, clear_utimer, 1 << 4);
 set_clear_csr!(
     /// Supervisor Timer Interrupt Enable
     , set_stimer, clear_stimer, 1 << 5);
@@ -0,0 +1,56 @@
+//! Branch Prediction Mode CSR (SiFive custom)
+
+use bit_field::BitField;
+
+/// Branch Prediction Mode register
+#[derive(Clone, Copy, Debug)]
+pub struct Bpm {
+    bits: usize,
+}
+
+/// Branch-Direction Prediction
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BDP {
+    /// Dynamic direction prediction
+    Dynamic = 0,
+    /// Static-taken direction prediction
+    StaticTaken = 1,
+}
+
+impl Bpm {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Branch-Direction Prediction
+    ///
+    /// Determines the value returned by the BHT component of the branch prediction system.
+    #[inline]
+    pub fn bdp(&self) -> BDP {
+        match self.bits.get_bit(0) {
+            true => BDP::StaticTaken,
+            false => BDP::Dynamic,
+        }
+    }
+}
+
+read_csr_as!(Bpm, 0x7C0);
+set!(0x7C0);
+clear!(0x7C0);
+
+/// Branch-Direction Prediction
+#[inline]
+pub unsafe fn set_bdp(bdp: BDP) {
+    match bdp {
+        BDP::StaticTaken => _set(1 << 0),
+        BDP::Dynamic => _clear(1 << 0),
+    }
+}
@@ -0,0 +1,77 @@
+//! Feature Disable CSR (SiFive custom)
+//!
+//! Each bit of this register disables a microarchitectural feature when set.
+//! It resets to zero (i.e., all the features enabled).
+
+use bit_field::BitField;
+
+/// Feature Disable register
+#[derive(Clone, Copy, Debug)]
+pub struct FeatureDisable {
+    bits: usize,
+}
+
+impl FeatureDisable {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Disable data cache clock gating
+    #[inline]
+    pub fn data_cache_clock_gating(&self) -> bool {
+        self.bits.get_bit(0)
+    }
+
+    /// Disable instruction cache clock gating
+    #[inline]
+    pub fn instruction_cache_clock_gating(&self) -> bool {
+        self.bits.get_bit(1)
+    }
+
+    /// Disable pipeline clock gating
+    #[inline]
+    pub fn pipeline_clock_gating(&self) -> bool {
+        self.bits.get_bit(2)
+    }
+
+    /// Disable speculative instruction cache refill
+    #[inline]
+    pub fn speculative_icache_refill(&self) -> bool {
+        self.bits.get_bit(3)
+    }
+
+    /// Suppress corrupt signal on GrantData messages
+    #[inline]
+    pub fn grant_data_corrupt(&self) -> bool {
+        self.bits.get_bit(9)
+    }
+}
+
+read_csr_as!(FeatureDisable, 0x7C1);
+write_csr_as_usize!(0x7C1);
+set!(0x7C1);
+clear!(0x7C1);
+
+set_clear_csr_imm!(
+    /// Disable data cache clock gating
+    , 0x7C1, set_data_cache_clock_gating, clear_data_cache_clock_gating, 1 << 0);
+set_clear_csr_imm!(
+    /// Disable instruction cache clock gating
+    , 0x7C1, set_instruction_cache_clock_gating, clear_instruction_cache_clock_gating, 1 << 1);
+set_clear_csr_imm!(
+    /// Disable pipeline clock gating
+    , 0x7C1, set_pipeline_clock_gating, clear_pipeline_clock_gating, 1 << 2);
+set_clear_csr_imm!(
+    /// Disable speculative instruction cache refill
+    , 0x7C1, set_speculative_icache_refill, clear_speculative_icache_refill, 1 << 3);
+set_clear_csr!(
+    /// Suppress corrupt signal on GrantData messages
+    , set_grant_data_corrupt, clear_grant_data_corrupt, 1 << 9);
@@ -0,0 +1,80 @@
+//! SiFive custom CSRs and instructions
+//!
+//! These registers and instructions are not part of the RISC-V standard.
+//! They are implemented by SiFive cores (e.g., E31, E51, U54 and U74).
+//! Accessing them on any other core raises an illegal instruction exception.
+
+pub mod bpm;
+pub mod feature_disable;
+
+/// `CFLUSH.D.L1` instruction wrapper
+///
+/// Writes back and invalidates the L1 data cache line containing the virtual address `addr`.
+/// The address is translated and permission checked as a load would be.
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn cflush_d_l1(addr: usize) {
+    match () {
+        // cflush.d.l1 rs1
+        #[cfg(riscv)]
+        () => core::arch::asm!(".insn i 0x73, 0, x0, {0}, -64", in(reg) addr),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// `CFLUSH.D.L1` instruction wrapper (all lines)
+///
+/// Writes back and invalidates all the lines of the L1 data cache.
+#[inline]
+pub unsafe fn cflush_d_l1_all() {
+    match () {
+        // cflush.d.l1 x0
+        #[cfg(riscv)]
+        () => core::arch::asm!(".word 0xFC000073"),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// `CDISCARD.D.L1` instruction wrapper
+///
+/// Invalidates the L1 data cache line containing the virtual address `addr` **without** writing it back.
+/// The address is translated and permission checked as a store would be.
+///
+/// # Safety
+///
+/// Any dirty data in the discarded line is lost.
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn cdiscard_d_l1(addr: usize) {
+    match () {
+        // cdiscard.d.l1 rs1
+        #[cfg(riscv)]
+        () => core::arch::asm!(".insn i 0x73, 0, x0, {0}, -62", in(reg) addr),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// `CDISCARD.D.L1` instruction wrapper (all lines)
+///
+/// Invalidates all the lines of the L1 data cache **without** writing them back.
+///
+/// # Safety
+///
+/// Any dirty data in the L1 data cache is lost.
+#[inline]
+pub unsafe fn cdiscard_d_l1_all() {
+    match () {
+        // cdiscard.d.l1 x0
+        #[cfg(riscv)]
+        () => core::arch::asm!(".word 0xFC200073"),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
@@ -9,6 +9,12 @@ pub struct Sip {
 }
 
 impl Sip {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -53,3 +59,13 @@ impl Sip {
 }
 
 read_csr_as!(Sip, 0x144);
+
+set_clear_csr_imm!(
+    /// User Software Interrupt Pending
+    , 0x144, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
+    /// Supervisor Software Interrupt Pending
+    ///
+    /// Supervisor timer and external interrupt pending bits are read-only in `sip`.
+    /// Only M-mode can inject them, via [`mip::inject`](super::mip::inject).
+    , 0x144, set_ssoft, clear_ssoft, 1 << 1);
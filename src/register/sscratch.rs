@@ -1,4 +1,25 @@
 //! sscratch register
+//!
+//! S-mode kernels typically keep the kernel stack pointer of the current hart in `sscratch`
+//! while running user code. On trap entry, the handler swaps `sp` and `sscratch` with a single
+//! `csrrw` instruction. See [`crate::swap_sp_sscratch`] for a ready-to-use assembly snippet.
 
 read_csr_as_usize!(0x140);
 write_csr_as_usize!(0x140);
+
+/// Atomically swaps the CSR with `bits` and returns its previous value
+#[inline]
+#[allow(unused_variables)]
+pub unsafe fn swap(bits: usize) -> usize {
+    match () {
+        #[cfg(riscv)]
+        () => {
+            let r: usize;
+            core::arch::asm!("csrrw {0}, sscratch, {1}", out(reg) r, in(reg) bits);
+            r
+        }
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
@@ -1,6 +1,7 @@
 //! sstatus register
 
 pub use super::mstatus::FS;
+use super::xlen::{self, Xlen};
 use bit_field::BitField;
 
 /// Supervisor Status Register
@@ -17,34 +18,46 @@ pub enum SPP {
 }
 
 impl Sstatus {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub const fn bits(&self) -> usize {
+        self.bits
+    }
+
     /// User Interrupt Enable
     #[inline]
-    pub fn uie(&self) -> bool {
-        self.bits.get_bit(0)
+    pub const fn uie(&self) -> bool {
+        self.bits & (1 << 0) != 0
     }
 
     /// Supervisor Interrupt Enable
     #[inline]
-    pub fn sie(&self) -> bool {
-        self.bits.get_bit(1)
+    pub const fn sie(&self) -> bool {
+        self.bits & (1 << 1) != 0
     }
 
     /// User Previous Interrupt Enable
     #[inline]
-    pub fn upie(&self) -> bool {
-        self.bits.get_bit(4)
+    pub const fn upie(&self) -> bool {
+        self.bits & (1 << 4) != 0
     }
 
     /// Supervisor Previous Interrupt Enable
     #[inline]
-    pub fn spie(&self) -> bool {
-        self.bits.get_bit(5)
+    pub const fn spie(&self) -> bool {
+        self.bits & (1 << 5) != 0
     }
 
     /// Supervisor Previous Privilege Mode
     #[inline]
-    pub fn spp(&self) -> SPP {
-        match self.bits.get_bit(8) {
+    pub const fn spp(&self) -> SPP {
+        match self.bits & (1 << 8) != 0 {
             true => SPP::Supervisor,
             false => SPP::User,
         }
@@ -52,8 +65,8 @@ impl Sstatus {
 
     /// The status of the floating-point unit
     #[inline]
-    pub fn fs(&self) -> FS {
-        match self.bits.get_bits(13..15) {
+    pub const fn fs(&self) -> FS {
+        match (self.bits >> 13) & 0b11 {
             0 => FS::Off,
             1 => FS::Initial,
             2 => FS::Clean,
@@ -65,8 +78,8 @@ impl Sstatus {
     /// The status of additional user-mode extensions
     /// and associated state
     #[inline]
-    pub fn xs(&self) -> FS {
-        match self.bits.get_bits(15..17) {
+    pub const fn xs(&self) -> FS {
+        match (self.bits >> 15) & 0b11 {
             0 => FS::Off,
             1 => FS::Initial,
             2 => FS::Clean,
@@ -77,21 +90,30 @@ impl Sstatus {
 
     /// Permit Supervisor User Memory access
     #[inline]
-    pub fn sum(&self) -> bool {
-        self.bits.get_bit(18)
+    pub const fn sum(&self) -> bool {
+        self.bits & (1 << 18) != 0
     }
 
     /// Make eXecutable Readable
     #[inline]
-    pub fn mxr(&self) -> bool {
-        self.bits.get_bit(19)
+    pub const fn mxr(&self) -> bool {
+        self.bits & (1 << 19) != 0
+    }
+
+    /// Supervisor Disable Trap (Ssdbltrp)
+    ///
+    /// Set by hardware when a trap is taken into S-mode. The trap handler clears it once it has
+    /// saved enough state to handle a nested trap. It is read-only zero unless `menvcfg.DTE` is set.
+    #[inline]
+    pub const fn sdt(&self) -> bool {
+        self.bits & (1 << 24) != 0
     }
 
     /// Whether either the FS field or XS field
     /// signals the presence of some dirty state
     #[inline]
-    pub fn sd(&self) -> bool {
-        self.bits.get_bit(usize::BITS as usize - 1)
+    pub const fn sd(&self) -> bool {
+        Xlen::NATIVE.msb(xlen::to_u64(self.bits))
     }
 }
 
@@ -100,15 +122,15 @@ write_csr!(0x100);
 set!(0x100);
 clear!(0x100);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Interrupt Enable
-    , set_uie, clear_uie, 1 << 0);
-set_clear_csr!(
+    , 0x100, set_uie, clear_uie, 1 << 0);
+set_clear_csr_imm!(
     /// Supervisor Interrupt Enable
-    , set_sie, clear_sie, 1 << 1);
-set_csr!(
+    , 0x100, set_sie, clear_sie, 1 << 1);
+set_csr_imm!(
     /// User Previous Interrupt Enable
-    , set_upie, 1 << 4);
+    , 0x100, set_upie, 1 << 4);
 set_csr!(
     /// Supervisor Previous Interrupt Enable
     , set_spie, 1 << 5);
@@ -118,6 +140,9 @@ set_clear_csr!(
 set_clear_csr!(
     /// Make eXecutable Readable
     , set_mxr, clear_mxr, 1 << 19);
+set_clear_csr!(
+    /// Supervisor Disable Trap (Ssdbltrp)
+    , set_sdt, clear_sdt, 1 << 24);
 
 /// Supervisor Previous Privilege Mode
 #[inline]
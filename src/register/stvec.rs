@@ -9,6 +9,12 @@ pub struct Stvec {
 }
 
 impl Stvec {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -18,18 +24,16 @@ impl Stvec {
     /// Returns the trap-vector base-address
     #[inline]
     pub fn address(&self) -> usize {
-        self.bits - (self.bits & 0b11)
+        match self.trap_mode() {
+            Some(mode) => self.bits & !mode.mask(),
+            None => self.bits & !0b11,
+        }
     }
 
     /// Returns the trap-vector mode
     #[inline]
     pub fn trap_mode(&self) -> Option<TrapMode> {
-        let mode = self.bits & 0b11;
-        match mode {
-            0 => Some(TrapMode::Direct),
-            1 => Some(TrapMode::Vectored),
-            _ => None,
-        }
+        TrapMode::from_tvec(self.bits)
     }
 }
 
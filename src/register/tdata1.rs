@@ -0,0 +1,91 @@
+//! tdata1 register
+//!
+//! First trigger data register of the trigger currently selected by `tselect`.
+
+use bit_field::BitField;
+
+/// tdata1 register
+#[derive(Clone, Copy, Debug)]
+pub struct Tdata1 {
+    bits: usize,
+}
+
+/// Trigger type
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Type {
+    /// There is no trigger at this `tselect`
+    None = 0,
+    /// Legacy SiFive address match trigger
+    Legacy = 1,
+    /// Address/data match trigger (`mcontrol`)
+    Mcontrol = 2,
+    /// Instruction count trigger (`icount`)
+    Icount = 3,
+    /// Interrupt trigger (`itrigger`)
+    Itrigger = 4,
+    /// Exception trigger (`etrigger`)
+    Etrigger = 5,
+    /// Address/data match trigger (`mcontrol6`)
+    Mcontrol6 = 6,
+    /// External trigger (`tmexttrigger`)
+    Tmexttrigger = 7,
+    /// The trigger exists but is currently disabled
+    Disabled = 15,
+}
+
+impl Tdata1 {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// Trigger type
+    ///
+    /// Returns [`None`] if the type is reserved by the specification.
+    #[inline]
+    pub fn trigger_type(&self) -> Option<Type> {
+        let xlen = usize::BITS as usize;
+        match self.bits.get_bits(xlen - 4..xlen) {
+            0 => Some(Type::None),
+            1 => Some(Type::Legacy),
+            2 => Some(Type::Mcontrol),
+            3 => Some(Type::Icount),
+            4 => Some(Type::Itrigger),
+            5 => Some(Type::Etrigger),
+            6 => Some(Type::Mcontrol6),
+            7 => Some(Type::Tmexttrigger),
+            15 => Some(Type::Disabled),
+            _ => None,
+        }
+    }
+
+    /// Debug Mode only
+    ///
+    /// If set, only Debug Mode can write the currently selected trigger registers.
+    #[inline]
+    pub fn dmode(&self) -> bool {
+        self.bits.get_bit(usize::BITS as usize - 5)
+    }
+
+    /// Trigger-specific data
+    #[inline]
+    pub fn data(&self) -> usize {
+        self.bits.get_bits(0..usize::BITS as usize - 5)
+    }
+}
+
+read_csr_as!(Tdata1, 0x7A1);
+write_csr!(0x7A1);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
@@ -0,0 +1,12 @@
+//! tdata2 register
+//!
+//! Second trigger data register of the trigger currently selected by `tselect`.
+
+read_csr_as_usize!(0x7A2);
+write_csr!(0x7A2);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
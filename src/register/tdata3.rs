@@ -0,0 +1,12 @@
+//! tdata3 register
+//!
+//! Third trigger data register of the trigger currently selected by `tselect`.
+
+read_csr_as_usize!(0x7A3);
+write_csr!(0x7A3);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
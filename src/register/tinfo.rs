@@ -0,0 +1,50 @@
+//! tinfo register
+//!
+//! Reports the trigger types supported by the trigger currently selected by `tselect`.
+
+use super::tdata1::Type;
+use bit_field::BitField;
+
+/// tinfo register
+#[derive(Clone, Copy, Debug)]
+pub struct Tinfo {
+    bits: usize,
+}
+
+impl Tinfo {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
+    /// Returns the contents of the register as raw bits
+    #[inline]
+    pub fn bits(&self) -> usize {
+        self.bits
+    }
+
+    /// One bit for each trigger type supported by the selected trigger
+    ///
+    /// If the currently selected trigger does not exist, this field contains `1`.
+    #[inline]
+    pub fn info(&self) -> u16 {
+        self.bits.get_bits(0..16) as u16
+    }
+
+    /// Returns `true` if the selected trigger supports the given type
+    #[inline]
+    pub fn supports(&self, trigger_type: Type) -> bool {
+        self.info().get_bit(trigger_type as usize)
+    }
+
+    /// Debug Specification version of the trigger module
+    ///
+    /// `0` means version 0.13 or earlier, `1` means version 1.0.
+    #[inline]
+    pub fn version(&self) -> usize {
+        self.bits.get_bits(24..32)
+    }
+}
+
+read_csr_as!(Tinfo, 0x7A4);
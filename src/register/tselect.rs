@@ -0,0 +1,12 @@
+//! tselect register
+//!
+//! Selects which trigger is accessed through the `tdata1`, `tdata2`, `tdata3` and `tinfo` registers.
+
+read_csr_as_usize!(0x7A0);
+write_csr!(0x7A0);
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
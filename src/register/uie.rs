@@ -9,6 +9,12 @@ pub struct Uie {
 }
 
 impl Uie {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// Returns the contents of the register as raw bits
     #[inline]
     pub fn bits(&self) -> usize {
@@ -38,12 +44,12 @@ read_csr_as!(Uie, 0x004);
 set!(0x004);
 clear!(0x004);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Software Interrupt Enable
-    , set_usoft, clear_usoft, 1 << 0);
-set_clear_csr!(
+    , 0x004, set_usoft, clear_usoft, 1 << 0);
+set_clear_csr_imm!(
     /// User Timer Interrupt Enable
-    , set_utimer, clear_utimer, 1 << 4);
+    , 0x004, set_utimer, clear_utimer, 1 << 4);
 set_clear_csr!(
     /// User External Interrupt Enable
     , set_uext, clear_uext, 1 << 8);
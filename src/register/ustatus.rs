@@ -10,6 +10,12 @@ pub struct Ustatus {
 }
 
 impl Ustatus {
+    /// Creates a register value from raw bits (e.g., captured in a crash dump)
+    #[inline]
+    pub const fn from_bits(bits: usize) -> Self {
+        Self { bits }
+    }
+
     /// User Interrupt Enable
     #[inline]
     pub fn uie(&self) -> bool {
@@ -28,10 +34,10 @@ write_csr!(0x000);
 set!(0x000);
 clear!(0x000);
 
-set_clear_csr!(
+set_clear_csr_imm!(
     /// User Interrupt Enable
-    , set_uie, clear_uie, 1 << 0);
+    , 0x000, set_uie, clear_uie, 1 << 0);
 
-set_csr!(
+set_csr_imm!(
     /// User Previous Interrupt Enable
-    , set_upie, 1 << 4);
+    , 0x000, set_upie, 1 << 4);
@@ -0,0 +1,13 @@
+//! VexRiscv custom CSRs
+//!
+//! These registers are not part of the RISC-V standard.
+//! They are implemented by VexRiscv cores with the external interrupt array plugin
+//! (e.g., in LiteX SoCs). Each bit corresponds to an external interrupt line.
+//! The machine (or supervisor) external interrupt is pending whenever
+//! an interrupt line is both pending and unmasked.
+//! Accessing them on any other core raises an illegal instruction exception.
+
+pub mod mmask;
+pub mod mpending;
+pub mod smask;
+pub mod spending;
@@ -0,0 +1,6 @@
+//! mpending register (VexRiscv custom)
+//!
+//! Each set bit indicates that the corresponding external interrupt line is pending.
+//! The register is read-only. Interrupt lines are cleared at their source.
+
+read_csr_as_usize!(0xFC0);
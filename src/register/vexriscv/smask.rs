@@ -0,0 +1,36 @@
+//! smask register (VexRiscv custom)
+//!
+//! Each set bit unmasks the corresponding external interrupt line
+//! for supervisor external interrupts.
+
+read_csr_as_usize!(0x9C0);
+write_csr!(0x9C0);
+set!(0x9C0);
+clear!(0x9C0);
+
+/// Writes the CSR
+///
+/// # Safety
+///
+/// Unmasking interrupt lines can break mask-based critical sections.
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+/// Unmasks the external interrupt lines of `bits`
+///
+/// # Safety
+///
+/// Unmasking interrupt lines can break mask-based critical sections.
+#[inline]
+pub unsafe fn set(bits: usize) {
+    _set(bits)
+}
+
+/// Masks the external interrupt lines of `bits`
+#[inline]
+pub fn clear(bits: usize) {
+    // SAFETY: atomic CSRRC instruction with no side effects
+    unsafe { _clear(bits) }
+}
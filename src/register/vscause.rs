@@ -0,0 +1,20 @@
+//! vscause register
+//!
+//! Virtual Supervisor Cause register. It has the same layout as `scause`.
+
+pub use super::scause::{Exception, Interrupt, Scause as Vscause, Trap};
+
+read_csr!(0x242);
+write_csr!(0x242);
+
+/// Reads the CSR
+#[inline]
+pub fn read() -> Vscause {
+    Vscause::from_bits(unsafe { _read() })
+}
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
@@ -0,0 +1,4 @@
+//! vsepc register
+
+read_csr_as_usize!(0x241);
+write_csr_as_usize!(0x241);
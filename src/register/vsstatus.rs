@@ -0,0 +1,39 @@
+//! vsstatus register
+//!
+//! Virtual Supervisor Status register. It replaces `sstatus` while running in a virtualized mode,
+//! and has the same layout.
+
+pub use super::sstatus::{Sstatus as Vsstatus, FS, SPP};
+
+read_csr!(0x200);
+write_csr!(0x200);
+set!(0x200);
+clear!(0x200);
+
+/// Reads the CSR
+#[inline]
+pub fn read() -> Vsstatus {
+    Vsstatus::from_bits(unsafe { _read() })
+}
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(bits: usize) {
+    _write(bits)
+}
+
+set_clear_csr_imm!(
+    /// Supervisor Interrupt Enable
+    , 0x200, set_sie, clear_sie, 1 << 1);
+set_clear_csr!(
+    /// Supervisor Previous Interrupt Enable
+    , set_spie, clear_spie, 1 << 5);
+
+/// Supervisor Previous Privilege Mode
+#[inline]
+pub unsafe fn set_spp(spp: SPP) {
+    match spp {
+        SPP::Supervisor => _set(1 << 8),
+        SPP::User => _clear(1 << 8),
+    }
+}
@@ -0,0 +1,20 @@
+//! vstvec register
+//!
+//! Virtual Supervisor Trap Vector Base Address register. It has the same layout as `stvec`.
+
+pub use super::stvec::{Stvec as Vstvec, TrapMode};
+
+read_csr!(0x205);
+write_csr!(0x205);
+
+/// Reads the CSR
+#[inline]
+pub fn read() -> Vstvec {
+    Vstvec::from_bits(unsafe { _read() })
+}
+
+/// Writes the CSR
+#[inline]
+pub unsafe fn write(addr: usize, mode: TrapMode) {
+    _write(addr + mode as usize);
+}
@@ -0,0 +1,103 @@
+//! XLEN-generic register values
+//!
+//! CSR values have the width of the native XLEN, which is represented with [`RegValue`]
+//! (i.e., `usize`). Thus, the meaning of a raw value silently changes between 32-bit and 64-bit
+//! targets. [`Xlen`] makes the layout explicit, so decoding code paths can be compiled and tested
+//! for both layouts on any target (e.g., on the host).
+//!
+//! # Example
+//!
+//! ```
+//! use riscv::register::xlen::Xlen;
+//!
+//! // the interrupt bit of mcause is the most significant bit
+//! assert!(Xlen::Xlen32.msb(0x8000_0007));
+//! assert!(!Xlen::Xlen64.msb(0x8000_0007));
+//! assert_eq!(Xlen::Xlen32.clear_msb(0x8000_0007), 7);
+//! ```
+
+/// Raw value of a CSR with the native XLEN.
+pub type RegValue = usize;
+
+/// Width of the integer registers.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Xlen {
+    /// 32-bit layout
+    Xlen32,
+    /// 64-bit layout
+    Xlen64,
+}
+
+impl Xlen {
+    /// Native XLEN of the target.
+    #[cfg(target_pointer_width = "32")]
+    pub const NATIVE: Self = Self::Xlen32;
+    /// Native XLEN of the target.
+    #[cfg(target_pointer_width = "64")]
+    pub const NATIVE: Self = Self::Xlen64;
+
+    /// Returns the number of bits of the layout.
+    #[inline]
+    pub const fn bits(self) -> u32 {
+        match self {
+            Self::Xlen32 => 32,
+            Self::Xlen64 => 64,
+        }
+    }
+
+    /// Returns a mask with all the bits of the layout set.
+    #[inline]
+    pub const fn mask(self) -> u64 {
+        u64::MAX >> (64 - self.bits())
+    }
+
+    /// Truncates a value to the layout.
+    #[inline]
+    pub const fn truncate(self, value: u64) -> u64 {
+        value & self.mask()
+    }
+
+    /// Checks if the most significant bit of a value is set.
+    #[inline]
+    pub const fn msb(self, value: u64) -> bool {
+        (value >> (self.bits() - 1)) & 1 == 1
+    }
+
+    /// Clears the most significant bit of a value and truncates it to the layout.
+    #[inline]
+    pub const fn clear_msb(self, value: u64) -> u64 {
+        self.truncate(value) & (self.mask() >> 1)
+    }
+
+    /// Returns the `width` most significant bits of a value (e.g., `misa.MXL` or `satp.MODE`).
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv::register::xlen::Xlen;
+    ///
+    /// assert_eq!(Xlen::Xlen32.top_bits(0x4000_0000, 2), 1);
+    /// assert_eq!(Xlen::Xlen64.top_bits(0x8000_0000_0000_0000, 2), 2);
+    /// ```
+    #[inline]
+    pub const fn top_bits(self, value: u64, width: u32) -> u64 {
+        self.truncate(value) >> (self.bits() - width)
+    }
+}
+
+/// Converts a native register value to a 64-bit value.
+#[inline]
+pub const fn to_u64(value: RegValue) -> u64 {
+    value as u64
+}
+
+/// Converts a 64-bit value to a native register value.
+/// It returns [`None`] if the value does not fit in the native XLEN.
+#[inline]
+pub const fn from_u64(value: u64) -> Option<RegValue> {
+    if value & !Xlen::NATIVE.mask() == 0 {
+        Some(value as RegValue)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,164 @@
+//! Random number generation from the Zkr entropy source
+//!
+//! [`ZkrRng`] polls the [`seed`](crate::register::seed) CSR repeatedly and concatenates
+//! its 16-bit entropy samples. It implements the `RngCore` and `CryptoRng` traits of `rand_core`,
+//! so crypto libraries can use it directly as a seed source.
+//!
+//! # Conditioning
+//!
+//! The samples of the entropy source are not full entropy. Thus, the output of this RNG must be
+//! conditioned before using it as key material. Following the Zkr specification, collect at least
+//! twice as many bits as required (e.g., 512 bits for a 256-bit seed) and compress them with a vetted
+//! conditioner (e.g., SHA-256), or use them to seed a cryptographically secure DRBG.
+//!
+//! # Note
+//!
+//! This module requires the `rand_core` feature.
+//!
+//! The `seed` CSR is only accessible in M-mode, unless access is granted to lower privilege modes
+//! via `mseccfg.SSEED` and `mseccfg.USEED`.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::rng::ZkrRng;
+//!
+//! let mut rng = ZkrRng::new();
+//! let mut seed = [0; 64]; // 512 bits of raw entropy for a 256-bit DRBG seed
+//! rng.try_fill_bytes(&mut seed).expect("entropy source failed");
+//! ```
+
+use crate::register::seed::{self, OPST};
+use core::fmt;
+use core::num::NonZeroU32;
+
+/// Error of the entropy source: it failed its self-test (`OPST` is `DEAD`) and will not recover.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("entropy source is dead")
+    }
+}
+
+impl From<Error> for rand_core::Error {
+    #[inline]
+    fn from(_: Error) -> Self {
+        // CUSTOM_START is non-zero
+        NonZeroU32::new(rand_core::Error::CUSTOM_START)
+            .unwrap()
+            .into()
+    }
+}
+
+/// Random number generator backed by the `seed` CSR (Zkr)
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZkrRng {
+    _private: (),
+}
+
+impl ZkrRng {
+    /// Creates a new RNG backed by the `seed` CSR.
+    #[inline]
+    pub const fn new() -> Self {
+        Self { _private: () }
+    }
+
+    /// Polls the entropy source until a 16-bit sample is available.
+    ///
+    /// It busy-waits while the entropy source is running its self-test or has no entropy available.
+    #[inline]
+    pub fn try_next_u16(&mut self) -> Result<u16, Error> {
+        loop {
+            let seed = seed::read();
+            match seed.opst() {
+                OPST::Es16 => return Ok(seed.entropy()),
+                OPST::Dead => return Err(Error),
+                OPST::Bist | OPST::Wait => core::hint::spin_loop(),
+            }
+        }
+    }
+
+    /// Returns 32 bits of raw entropy.
+    #[inline]
+    pub fn try_next_u32(&mut self) -> Result<u32, Error> {
+        let lo = self.try_next_u16()? as u32;
+        let hi = self.try_next_u16()? as u32;
+        Ok((hi << 16) | lo)
+    }
+
+    /// Returns 64 bits of raw entropy.
+    #[inline]
+    pub fn try_next_u64(&mut self) -> Result<u64, Error> {
+        let lo = self.try_next_u32()? as u64;
+        let hi = self.try_next_u32()? as u64;
+        Ok((hi << 32) | lo)
+    }
+
+    /// Fills `dest` with raw entropy.
+    #[inline]
+    pub fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        for chunk in dest.chunks_mut(2) {
+            let sample = self.try_next_u16()?.to_le_bytes();
+            chunk.copy_from_slice(&sample[..chunk.len()]);
+        }
+        Ok(())
+    }
+
+    /// Returns 32 bits of raw entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entropy source is dead.
+    #[inline]
+    pub fn next_u32(&mut self) -> u32 {
+        self.try_next_u32().unwrap()
+    }
+
+    /// Returns 64 bits of raw entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entropy source is dead.
+    #[inline]
+    pub fn next_u64(&mut self) -> u64 {
+        self.try_next_u64().unwrap()
+    }
+
+    /// Fills `dest` with raw entropy.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entropy source is dead.
+    #[inline]
+    pub fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.try_fill_bytes(dest).unwrap()
+    }
+}
+
+impl rand_core::RngCore for ZkrRng {
+    #[inline]
+    fn next_u32(&mut self) -> u32 {
+        ZkrRng::next_u32(self)
+    }
+
+    #[inline]
+    fn next_u64(&mut self) -> u64 {
+        ZkrRng::next_u64(self)
+    }
+
+    #[inline]
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        ZkrRng::fill_bytes(self, dest)
+    }
+
+    #[inline]
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        Ok(ZkrRng::try_fill_bytes(self, dest)?)
+    }
+}
+
+/// The output is raw entropy from the platform entropy source. See the conditioning
+/// requirements in the [module documentation](self) before using it as key material.
+impl rand_core::CryptoRng for ZkrRng {}
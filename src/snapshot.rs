@@ -0,0 +1,342 @@
+//! CSR snapshots
+//!
+//! A [`Csrs`] snapshot captures the value of a set of machine-level CSRs.
+//! Comparing two snapshots with [`Csrs::diff`] reports the registers and fields that changed.
+//! This is useful to find out, for instance, what a bootloader changed before jumping to the application.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::snapshot::{Csr, Csrs};
+//!
+//! let before = Csrs::capture([Csr::Mstatus, Csr::Mie, Csr::Mtvec]);
+//! // ...
+//! let after = Csrs::capture([Csr::Mstatus, Csr::Mie, Csr::Mtvec]);
+//! for change in before.diff(&after) {
+//!     // e.g., "mstatus: 0x1800 -> 0x1808 (MIE)"
+//!     let _ = change;
+//! }
+//! ```
+
+use crate::register::{
+    mcause, mcounteren, medeleg, mepc, mideleg, mie, mip, misa, mscratch, mstatus, mtval, mtvec,
+    satp,
+};
+use core::fmt;
+
+/// Field of a CSR
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Field {
+    /// Name of the field, as in the RISC-V privileged specification
+    pub name: &'static str,
+    /// Position of the least significant bit of the field
+    pub offset: u32,
+    /// Number of bits of the field
+    pub width: u32,
+}
+
+impl Field {
+    const fn new(name: &'static str, offset: u32, width: u32) -> Self {
+        Self {
+            name,
+            offset,
+            width,
+        }
+    }
+
+    /// Returns the mask of the bits of the field
+    #[inline]
+    pub const fn mask(&self) -> usize {
+        match self.width {
+            w if w >= usize::BITS => usize::MAX,
+            w => ((1 << w) - 1) << self.offset,
+        }
+    }
+}
+
+const XLEN: u32 = usize::BITS;
+
+const MSTATUS_FIELDS: &[Field] = &[
+    Field::new("SIE", 1, 1),
+    Field::new("MIE", 3, 1),
+    Field::new("SPIE", 5, 1),
+    Field::new("MPIE", 7, 1),
+    Field::new("SPP", 8, 1),
+    Field::new("VS", 9, 2),
+    Field::new("MPP", 11, 2),
+    Field::new("FS", 13, 2),
+    Field::new("XS", 15, 2),
+    Field::new("MPRV", 17, 1),
+    Field::new("SUM", 18, 1),
+    Field::new("MXR", 19, 1),
+    Field::new("TVM", 20, 1),
+    Field::new("TW", 21, 1),
+    Field::new("TSR", 22, 1),
+    Field::new("SDT", 24, 1),
+    Field::new("SD", XLEN - 1, 1),
+];
+
+const MIE_FIELDS: &[Field] = &[
+    Field::new("SSIE", 1, 1),
+    Field::new("MSIE", 3, 1),
+    Field::new("STIE", 5, 1),
+    Field::new("MTIE", 7, 1),
+    Field::new("SEIE", 9, 1),
+    Field::new("MEIE", 11, 1),
+];
+
+const MIP_FIELDS: &[Field] = &[
+    Field::new("SSIP", 1, 1),
+    Field::new("MSIP", 3, 1),
+    Field::new("STIP", 5, 1),
+    Field::new("MTIP", 7, 1),
+    Field::new("SEIP", 9, 1),
+    Field::new("MEIP", 11, 1),
+];
+
+const MTVEC_FIELDS: &[Field] = &[Field::new("MODE", 0, 2), Field::new("BASE", 2, XLEN - 2)];
+
+const MCOUNTEREN_FIELDS: &[Field] = &[
+    Field::new("CY", 0, 1),
+    Field::new("TM", 1, 1),
+    Field::new("IR", 2, 1),
+    Field::new("HPM", 3, 29),
+];
+
+const MCAUSE_FIELDS: &[Field] = &[
+    Field::new("Exception Code", 0, XLEN - 1),
+    Field::new("Interrupt", XLEN - 1, 1),
+];
+
+#[cfg(target_pointer_width = "32")]
+const SATP_FIELDS: &[Field] = &[
+    Field::new("PPN", 0, 22),
+    Field::new("ASID", 22, 9),
+    Field::new("MODE", 31, 1),
+];
+
+#[cfg(not(target_pointer_width = "32"))]
+const SATP_FIELDS: &[Field] = &[
+    Field::new("PPN", 0, 44),
+    Field::new("ASID", 44, 16),
+    Field::new("MODE", 60, 4),
+];
+
+/// CSRs that can be captured in a snapshot
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Csr {
+    Mstatus,
+    Misa,
+    Medeleg,
+    Mideleg,
+    Mie,
+    Mtvec,
+    Mcounteren,
+    Mscratch,
+    Mepc,
+    Mcause,
+    Mtval,
+    Mip,
+    Satp,
+}
+
+impl Csr {
+    /// All the CSRs that can be captured in a snapshot
+    ///
+    /// `medeleg`, `mideleg` and `satp` only exist on harts with S-mode (see [`Csr::read`]).
+    pub const ALL: [Csr; 13] = [
+        Csr::Mstatus,
+        Csr::Misa,
+        Csr::Medeleg,
+        Csr::Mideleg,
+        Csr::Mie,
+        Csr::Mtvec,
+        Csr::Mcounteren,
+        Csr::Mscratch,
+        Csr::Mepc,
+        Csr::Mcause,
+        Csr::Mtval,
+        Csr::Mip,
+        Csr::Satp,
+    ];
+
+    /// Returns the name of the CSR
+    #[inline]
+    pub const fn name(self) -> &'static str {
+        match self {
+            Csr::Mstatus => "mstatus",
+            Csr::Misa => "misa",
+            Csr::Medeleg => "medeleg",
+            Csr::Mideleg => "mideleg",
+            Csr::Mie => "mie",
+            Csr::Mtvec => "mtvec",
+            Csr::Mcounteren => "mcounteren",
+            Csr::Mscratch => "mscratch",
+            Csr::Mepc => "mepc",
+            Csr::Mcause => "mcause",
+            Csr::Mtval => "mtval",
+            Csr::Mip => "mip",
+            Csr::Satp => "satp",
+        }
+    }
+
+    /// Returns the number of the CSR
+    #[inline]
+    pub const fn number(self) -> u16 {
+        match self {
+            Csr::Mstatus => 0x300,
+            Csr::Misa => 0x301,
+            Csr::Medeleg => 0x302,
+            Csr::Mideleg => 0x303,
+            Csr::Mie => 0x304,
+            Csr::Mtvec => 0x305,
+            Csr::Mcounteren => 0x306,
+            Csr::Mscratch => 0x340,
+            Csr::Mepc => 0x341,
+            Csr::Mcause => 0x342,
+            Csr::Mtval => 0x343,
+            Csr::Mip => 0x344,
+            Csr::Satp => 0x180,
+        }
+    }
+
+    /// Returns the known fields of the CSR.
+    /// CSRs with no fields (e.g., `mepc`) return an empty slice.
+    #[inline]
+    pub const fn fields(self) -> &'static [Field] {
+        match self {
+            Csr::Mstatus => MSTATUS_FIELDS,
+            Csr::Mie => MIE_FIELDS,
+            Csr::Mtvec => MTVEC_FIELDS,
+            Csr::Mcounteren => MCOUNTEREN_FIELDS,
+            Csr::Mcause => MCAUSE_FIELDS,
+            Csr::Mip => MIP_FIELDS,
+            Csr::Satp => SATP_FIELDS,
+            _ => &[],
+        }
+    }
+
+    /// Reads the CSR as raw bits.
+    /// If `misa` is not implemented, it returns 0.
+    ///
+    /// `medeleg`, `mideleg` and `satp` do not exist on harts without S-mode, and reading them
+    /// raises an illegal instruction exception. Thus, they are only read if `misa` reports the
+    /// S extension, and read as 0 otherwise. If `misa` is not implemented, they are always read.
+    #[inline]
+    pub fn read(self) -> usize {
+        match self {
+            Csr::Medeleg | Csr::Mideleg | Csr::Satp if !has_supervisor() => 0,
+            Csr::Mstatus => mstatus::read().bits(),
+            Csr::Misa => misa::read().map_or(0, |misa| misa.bits()),
+            Csr::Medeleg => medeleg::read().bits(),
+            Csr::Mideleg => mideleg::read().bits(),
+            Csr::Mie => mie::read().bits(),
+            Csr::Mtvec => mtvec::read().bits(),
+            Csr::Mcounteren => mcounteren::read().bits(),
+            Csr::Mscratch => mscratch::read(),
+            Csr::Mepc => mepc::read(),
+            Csr::Mcause => mcause::read().bits(),
+            Csr::Mtval => mtval::read(),
+            Csr::Mip => mip::read().bits(),
+            Csr::Satp => satp::read().bits(),
+        }
+    }
+}
+
+/// Checks whether the hart implements S-mode, according to `misa`
+#[inline]
+fn has_supervisor() -> bool {
+    misa::read().map_or(true, |misa| misa.has_extension('S'))
+}
+
+/// Snapshot of the value of `N` CSRs
+#[derive(Clone, Copy, Debug)]
+pub struct Csrs<const N: usize> {
+    values: [(Csr, usize); N],
+}
+
+impl<const N: usize> Csrs<N> {
+    /// Captures the current value of the given CSRs.
+    /// CSRs that only exist on harts with S-mode are captured as 0 on M-only harts (see [`Csr::read`]).
+    #[inline]
+    pub fn capture(csrs: [Csr; N]) -> Self {
+        let mut values = [(Csr::Mstatus, 0); N];
+        for (value, csr) in values.iter_mut().zip(csrs) {
+            *value = (csr, csr.read());
+        }
+        Self { values }
+    }
+
+    /// Returns the captured value of a CSR, if it is part of the snapshot
+    #[inline]
+    pub fn get(&self, csr: Csr) -> Option<usize> {
+        self.values
+            .iter()
+            .find(|(c, _)| *c == csr)
+            .map(|(_, value)| *value)
+    }
+
+    /// Returns an iterator over the captured CSRs and their values
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (Csr, usize)> + '_ {
+        self.values.iter().copied()
+    }
+
+    /// Returns an iterator over the CSRs that changed from `self` to `other`.
+    /// CSRs that are only captured in one of the snapshots are ignored.
+    #[inline]
+    pub fn diff<'a, const M: usize>(
+        &'a self,
+        other: &'a Csrs<M>,
+    ) -> impl Iterator<Item = Change> + 'a {
+        self.iter()
+            .filter_map(move |(csr, old)| match other.get(csr) {
+                Some(new) if new != old => Some(Change { csr, old, new }),
+                _ => None,
+            })
+    }
+}
+
+/// Change of a CSR between two snapshots
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Change {
+    /// Changed CSR
+    pub csr: Csr,
+    /// Value in the first snapshot
+    pub old: usize,
+    /// Value in the second snapshot
+    pub new: usize,
+}
+
+impl Change {
+    /// Returns the mask of the bits that changed
+    #[inline]
+    pub fn changed_bits(&self) -> usize {
+        self.old ^ self.new
+    }
+
+    /// Returns an iterator over the known fields of the CSR that changed
+    #[inline]
+    pub fn changed_fields(&self) -> impl Iterator<Item = &'static Field> {
+        let changed = self.changed_bits();
+        self.csr
+            .fields()
+            .iter()
+            .filter(move |field| changed & field.mask() != 0)
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {:#x} -> {:#x}", self.csr.name(), self.old, self.new)?;
+        let mut fields = self.changed_fields();
+        if let Some(first) = fields.next() {
+            write!(f, " ({}", first.name)?;
+            for field in fields {
+                write!(f, ", {}", field.name)?;
+            }
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
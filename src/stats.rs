@@ -0,0 +1,114 @@
+//! Per-source interrupt statistics
+//!
+//! # Note
+//!
+//! This module requires the `stats` feature.
+//!
+//! [`InterruptStats`] counts the occurrences of each interrupt source and records the
+//! [`Instant`] of the last one. It helps to spot interrupt storms and to debug systems in the field.
+//!
+//! Software must call [`InterruptStats::record`] at the beginning of the handler
+//! (or in the dispatcher of the trap handler).
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::stats::InterruptStats;
+//!
+//! static STATS: InterruptStats<4> = InterruptStats::new();
+//!
+//! fn source_1_handler() {
+//!     STATS.record(1);
+//!     // handle the interrupt here
+//! }
+//!
+//! fn report() {
+//!     let stats = STATS.stats(1);
+//!     if let Some(last) = stats.last {
+//!         let _ = (stats.count, last.elapsed());
+//!     }
+//! }
+//! ```
+use crate::time::Instant;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// Statistics of an interrupt source.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Stats {
+    /// Number of occurrences.
+    pub count: u32,
+    /// Instant of the last occurrence. It is [`None`] if the source has not occurred.
+    pub last: Option<Instant>,
+}
+
+impl Stats {
+    const fn new() -> Self {
+        Self {
+            count: 0,
+            last: None,
+        }
+    }
+}
+
+/// Statistics for `N` interrupt sources.
+pub struct InterruptStats<const N: usize> {
+    entries: Mutex<RefCell<[Stats; N]>>,
+}
+
+impl<const N: usize> InterruptStats<N> {
+    /// Creates a new, empty statistics set.
+    pub const fn new() -> Self {
+        Self {
+            entries: Mutex::new(RefCell::new([Stats::new(); N])),
+        }
+    }
+
+    /// Records an occurrence of the interrupt source.
+    /// It returns the updated number of occurrences.
+    #[inline]
+    pub fn record(&self, source: usize) -> u32 {
+        let now = Instant::now();
+        critical_section::with(|cs| {
+            let stats = &mut self.entries.borrow(cs).borrow_mut()[source];
+            stats.count = stats.count.saturating_add(1);
+            stats.last = Some(now);
+            stats.count
+        })
+    }
+
+    /// Returns the statistics of an interrupt source.
+    #[inline]
+    pub fn stats(&self, source: usize) -> Stats {
+        critical_section::with(|cs| self.entries.borrow(cs).borrow()[source])
+    }
+
+    /// Returns the total number of occurrences of all the interrupt sources.
+    #[inline]
+    pub fn total(&self) -> u32 {
+        critical_section::with(|cs| {
+            self.entries
+                .borrow(cs)
+                .borrow()
+                .iter()
+                .fold(0u32, |total, stats| total.saturating_add(stats.count))
+        })
+    }
+
+    /// Clears the statistics of all the interrupt sources.
+    #[inline]
+    pub fn reset(&self) {
+        critical_section::with(|cs| {
+            for stats in self.entries.borrow(cs).borrow_mut().iter_mut() {
+                *stats = Stats::new();
+            }
+        });
+    }
+}
+
+impl<const N: usize> Default for InterruptStats<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
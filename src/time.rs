@@ -0,0 +1,353 @@
+//! Time instants and durations over the 64-bit machine timer
+//!
+//! [`Instant`] and [`Duration`] are measured in timer ticks (i.e., `mtime` increments).
+//! Instants are compared and subtracted with wrapping arithmetic, so the results are correct
+//! around the 64-bit wraparound as long as the compared instants are less than 2^63 ticks apart.
+//!
+//! [`Clock`] converts instants to wall-clock time (i.e., seconds and nanoseconds) since boot
+//! or since an epoch anchored to a given instant.
+use crate::register::time;
+use core::cmp::Ordering;
+use core::ops::{Add, AddAssign, Sub, SubAssign};
+
+/// A measurement of the machine timer
+///
+/// # Example
+///
+/// ```
+/// use riscv::time::{Duration, Instant};
+///
+/// let before = Instant::from_ticks(u64::MAX - 1);
+/// let after = before + Duration::from_ticks(4); // wraps around
+/// assert!(after > before);
+/// assert_eq!(after - before, Duration::from_ticks(4));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Instant {
+    ticks: u64,
+}
+
+/// A span of time in machine timer ticks
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Duration {
+    ticks: u64,
+}
+
+impl Instant {
+    /// Returns the current instant, read through the `time` CSR.
+    #[inline]
+    pub fn now() -> Self {
+        Self::from_ticks(time::read64())
+    }
+
+    /// Creates an instant from a raw timer value.
+    #[inline]
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self { ticks }
+    }
+
+    /// Returns the raw timer value.
+    #[inline]
+    pub const fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`.
+    /// If `earlier` is after `self`, it returns [`None`].
+    #[inline]
+    pub fn checked_duration_since(&self, earlier: Self) -> Option<Duration> {
+        match self.cmp_wrapping(&earlier) {
+            Ordering::Less => None,
+            _ => Some(Duration::from_ticks(self.ticks.wrapping_sub(earlier.ticks))),
+        }
+    }
+
+    /// Returns the time elapsed from `earlier` to `self`, or zero if `earlier` is after `self`.
+    #[inline]
+    pub fn saturating_duration_since(&self, earlier: Self) -> Duration {
+        self.checked_duration_since(earlier).unwrap_or_default()
+    }
+
+    /// Returns the time elapsed since `self`.
+    #[inline]
+    pub fn elapsed(&self) -> Duration {
+        Self::now().saturating_duration_since(*self)
+    }
+
+    /// Compares two instants taking the timer wraparound into account.
+    #[inline]
+    fn cmp_wrapping(&self, other: &Self) -> Ordering {
+        (self.ticks.wrapping_sub(other.ticks) as i64).cmp(&0)
+    }
+}
+
+impl PartialOrd for Instant {
+    /// Instants are ordered with wrapping arithmetic.
+    /// The ordering is only meaningful for instants less than 2^63 ticks apart.
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp_wrapping(other))
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Duration) -> Self {
+        Self::from_ticks(self.ticks.wrapping_add(rhs.ticks))
+    }
+}
+
+impl AddAssign<Duration> for Instant {
+    #[inline]
+    fn add_assign(&mut self, rhs: Duration) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub<Duration> for Instant {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Duration) -> Self {
+        Self::from_ticks(self.ticks.wrapping_sub(rhs.ticks))
+    }
+}
+
+impl SubAssign<Duration> for Instant {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Duration) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sub for Instant {
+    type Output = Duration;
+
+    /// Returns the time elapsed from `rhs` to `self`, or zero if `rhs` is after `self`.
+    #[inline]
+    fn sub(self, rhs: Self) -> Duration {
+        self.saturating_duration_since(rhs)
+    }
+}
+
+impl Duration {
+    /// A duration of zero ticks.
+    pub const ZERO: Self = Self::from_ticks(0);
+
+    /// Creates a duration from timer ticks.
+    #[inline]
+    pub const fn from_ticks(ticks: u64) -> Self {
+        Self { ticks }
+    }
+
+    /// Returns the number of timer ticks.
+    #[inline]
+    pub const fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Creates a duration from microseconds for a timer running at `ticks_second` Hertz.
+    /// The result is rounded up to the next tick and saturates at [`u64::MAX`] ticks.
+    #[inline]
+    pub const fn from_micros(us: u64, ticks_second: u32) -> Self {
+        Self::from_ticks(Self::convert_up(us, ticks_second as u64, 1_000_000))
+    }
+
+    /// Creates a duration from milliseconds for a timer running at `ticks_second` Hertz.
+    /// The result is rounded up to the next tick and saturates at [`u64::MAX`] ticks.
+    #[inline]
+    pub const fn from_millis(ms: u64, ticks_second: u32) -> Self {
+        Self::from_ticks(Self::convert_up(ms, ticks_second as u64, 1_000))
+    }
+
+    /// Creates a duration from seconds for a timer running at `ticks_second` Hertz.
+    /// The result saturates at [`u64::MAX`] ticks.
+    #[inline]
+    pub const fn from_secs(secs: u64, ticks_second: u32) -> Self {
+        Self::from_ticks(secs.saturating_mul(ticks_second as u64))
+    }
+
+    /// Returns the number of whole microseconds for a timer running at `ticks_second` Hertz.
+    #[inline]
+    pub const fn as_micros(&self, ticks_second: u32) -> u64 {
+        (self.ticks as u128 * 1_000_000 / ticks_second as u128) as u64
+    }
+
+    /// Returns the number of whole milliseconds for a timer running at `ticks_second` Hertz.
+    #[inline]
+    pub const fn as_millis(&self, ticks_second: u32) -> u64 {
+        (self.ticks as u128 * 1_000 / ticks_second as u128) as u64
+    }
+
+    /// Returns the number of whole seconds for a timer running at `ticks_second` Hertz.
+    #[inline]
+    pub const fn as_secs(&self, ticks_second: u32) -> u64 {
+        self.ticks / ticks_second as u64
+    }
+
+    /// Checked addition. Returns [`None`] on overflow.
+    #[inline]
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.ticks.checked_add(rhs.ticks).map(Self::from_ticks)
+    }
+
+    /// Checked subtraction. Returns [`None`] if `rhs` is greater than `self`.
+    #[inline]
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.ticks.checked_sub(rhs.ticks).map(Self::from_ticks)
+    }
+
+    /// Converts `value / denominator` seconds to ticks, rounding up.
+    #[inline]
+    const fn convert_up(value: u64, ticks_second: u64, denominator: u64) -> u64 {
+        let ticks = (value as u128 * ticks_second as u128 + (denominator as u128 - 1))
+            / denominator as u128;
+        if ticks > u64::MAX as u128 {
+            u64::MAX
+        } else {
+            ticks as u64
+        }
+    }
+}
+
+impl Add for Duration {
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::from_ticks(self.ticks + rhs.ticks)
+    }
+}
+
+impl AddAssign for Duration {
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Duration {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_ticks(self.ticks - rhs.ticks)
+    }
+}
+
+impl SubAssign for Duration {
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Wall-clock time, in seconds and nanoseconds since an epoch.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct WallTime {
+    secs: u64,
+    nanos: u32,
+}
+
+impl WallTime {
+    /// Creates a new wall-clock time. Excess nanoseconds are carried over to seconds,
+    /// which saturate at `u64::MAX`.
+    #[inline]
+    pub const fn new(secs: u64, nanos: u32) -> Self {
+        Self {
+            secs: secs.saturating_add((nanos / 1_000_000_000) as u64),
+            nanos: nanos % 1_000_000_000,
+        }
+    }
+
+    /// Returns the whole seconds.
+    #[inline]
+    pub const fn secs(&self) -> u64 {
+        self.secs
+    }
+
+    /// Returns the nanoseconds within the second. It is always lower than 1_000_000_000.
+    #[inline]
+    pub const fn nanos(&self) -> u32 {
+        self.nanos
+    }
+}
+
+/// Wall clock over the machine timer
+///
+/// A clock anchors an epoch (e.g., the UNIX time obtained from a network) to an [`Instant`]
+/// and converts instants to [`WallTime`] with the timebase frequency of the platform.
+/// By default, the epoch is the boot (i.e., timer value 0).
+///
+/// # Example
+///
+/// ```
+/// use riscv::time::{Clock, Instant, WallTime};
+///
+/// let mut clock = Clock::new(10_000_000); // 10 MHz timebase
+/// assert_eq!(clock.at(Instant::from_ticks(25_000_000)), WallTime::new(2, 500_000_000));
+///
+/// clock.set_epoch(Instant::from_ticks(10_000_000), WallTime::new(1_700_000_000, 0));
+/// assert_eq!(clock.at(Instant::from_ticks(25_000_000)), WallTime::new(1_700_000_001, 500_000_000));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Clock {
+    ticks_second: u32,
+    anchor: Instant,
+    epoch: WallTime,
+}
+
+impl Clock {
+    /// Creates a clock for a timer running at `ticks_second` Hertz.
+    /// The epoch is the boot (i.e., timer value 0).
+    #[inline]
+    pub const fn new(ticks_second: u32) -> Self {
+        assert!(
+            ticks_second > 0,
+            "timebase frequency must be greater than 0"
+        );
+        Self {
+            ticks_second,
+            anchor: Instant::from_ticks(0),
+            epoch: WallTime::new(0, 0),
+        }
+    }
+
+    /// Returns the timebase frequency of the clock, in Hertz.
+    #[inline]
+    pub const fn frequency(&self) -> u32 {
+        self.ticks_second
+    }
+
+    /// Anchors the wall-clock time `time` to the instant `at`.
+    #[inline]
+    pub fn set_epoch(&mut self, at: Instant, time: WallTime) {
+        self.anchor = at;
+        self.epoch = time;
+    }
+
+    /// Returns the wall-clock time at the instant `instant`.
+    /// Instants before the anchor saturate to the anchored time.
+    ///
+    /// Use it with instants read from memory-mapped timers (e.g., `CLINT::now`).
+    #[inline]
+    pub fn at(&self, instant: Instant) -> WallTime {
+        let ticks = instant.saturating_duration_since(self.anchor).ticks();
+        let frequency = self.ticks_second as u64;
+        let secs = ticks / frequency;
+        // the remainder is lower than 2^32, so the product cannot overflow
+        let nanos = (ticks % frequency) * 1_000_000_000 / frequency;
+        WallTime::new(
+            self.epoch.secs.saturating_add(secs),
+            self.epoch.nanos + nanos as u32,
+        )
+    }
+
+    /// Returns the current wall-clock time, read through the `time` CSR.
+    #[inline]
+    pub fn now(&self) -> WallTime {
+        self.at(Instant::now())
+    }
+}
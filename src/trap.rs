@@ -0,0 +1,523 @@
+//! Trap diagnostics
+//!
+//! [`Report`] and [`SupervisorReport`] gather the CSRs that describe the last trap
+//! taken in M-mode and S-mode, respectively. They are meant to be read at the beginning
+//! of a trap handler (e.g., a default handler that prints a crash dump and halts).
+//! For the trap exit path, [`prepare_return`] sets up the CSRs used by `mret`.
+//! Bootloaders can delegate the usual traps to S-mode with [`delegate_standard_to_supervisor`].
+//!
+//! [`ExceptionHandler`] offers structured exception handling, with one method per exception.
+//! The [`crate::exception_handler`] macro installs an implementation as the exception handler.
+//! [`Reentrancy`] detects traps taken inside the trap handler (i.e., double faults).
+//! The [`decode`] module decodes the instruction that raised a memory access exception.
+//!
+//! # Example
+//!
+//! ``` no_run
+//! use riscv::trap::Report;
+//!
+//! fn default_handler() -> ! {
+//!     let report = Report::read();
+//!     panic!("unhandled trap: {}", report);
+//! }
+//! ```
+
+pub mod decode;
+
+use crate::register::{
+    mcause, medeleg, mepc, mhartid, mideleg, mstatus, mtval, scause, sepc, sstatus, stval,
+};
+use core::cell::UnsafeCell;
+use core::fmt;
+
+/// Prepares the return from a M-mode trap by writing `mstatus` and `mepc`.
+///
+/// `mstatus` is written first, and then `mepc`, with two consecutive CSR instructions.
+/// After this function, `mret` resumes execution at `mepc` with the privilege mode of `mstatus.MPP`,
+/// and restores `mstatus.MIE` from `mstatus.MPIE`. RTOS context switches can use it to
+/// restore the context of the next task right before `mret`.
+///
+/// # Safety
+///
+/// `mstatus` must have `MIE` cleared. Otherwise, an interrupt could be taken before `mret`,
+/// overwriting `mepc`. To re-enable interrupts after `mret`, set `MPIE` instead.
+/// Nothing may change `mstatus` or `mepc` between this function and `mret`.
+#[inline(always)]
+#[allow(unused_variables)]
+pub unsafe fn prepare_return(mstatus: usize, mepc: usize) {
+    debug_assert_eq!(mstatus & (1 << 3), 0, "mstatus.MIE must be cleared");
+    match () {
+        #[cfg(riscv)]
+        () => core::arch::asm!(
+            "csrw mstatus, {0}",
+            "csrw mepc, {1}",
+            in(reg) mstatus,
+            in(reg) mepc,
+            options(nostack),
+        ),
+
+        #[cfg(not(riscv))]
+        () => unimplemented!(),
+    }
+}
+
+/// Traps delegated to S-mode by [`delegate_standard_to_supervisor`].
+///
+/// [`Delegation::STANDARD`] (also the [`Default`]) matches the delegation set up by
+/// SBI implementations such as OpenSBI. Each field excludes its traps from delegation when cleared.
+/// Misaligned loads and stores, access faults and illegal instructions are never delegated,
+/// as M-mode firmware usually emulates or reports them.
+///
+/// # Example
+///
+/// ``` no_run
+/// use riscv::trap::{delegate_standard_to_supervisor, Delegation};
+///
+/// // the kernel debugger runs in M-mode
+/// let delegation = Delegation {
+///     breakpoint: false,
+///     ..Delegation::STANDARD
+/// };
+/// unsafe { delegate_standard_to_supervisor(delegation) };
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Delegation {
+    /// Instruction address misaligned exceptions
+    pub instruction_misaligned: bool,
+    /// Breakpoint exceptions
+    pub breakpoint: bool,
+    /// Environment calls from U-mode
+    pub user_env_call: bool,
+    /// Instruction, load and store/AMO page faults
+    pub page_faults: bool,
+    /// Supervisor software interrupts
+    pub supervisor_soft: bool,
+    /// Supervisor timer interrupts
+    pub supervisor_timer: bool,
+    /// Supervisor external interrupts
+    pub supervisor_external: bool,
+}
+
+impl Delegation {
+    /// Conventional delegation of an M-mode bootloader that runs a supervisor
+    pub const STANDARD: Self = Self {
+        instruction_misaligned: true,
+        breakpoint: true,
+        user_env_call: true,
+        page_faults: true,
+        supervisor_soft: true,
+        supervisor_timer: true,
+        supervisor_external: true,
+    };
+
+    /// Returns the value of `medeleg` for this delegation
+    #[inline]
+    pub const fn medeleg(&self) -> usize {
+        let mut bits = 0;
+        if self.instruction_misaligned {
+            bits |= 1 << 0;
+        }
+        if self.breakpoint {
+            bits |= 1 << 3;
+        }
+        if self.user_env_call {
+            bits |= 1 << 8;
+        }
+        if self.page_faults {
+            bits |= (1 << 12) | (1 << 13) | (1 << 15);
+        }
+        bits
+    }
+
+    /// Returns the value of `mideleg` for this delegation
+    #[inline]
+    pub const fn mideleg(&self) -> usize {
+        let mut bits = 0;
+        if self.supervisor_soft {
+            bits |= 1 << 1;
+        }
+        if self.supervisor_timer {
+            bits |= 1 << 5;
+        }
+        if self.supervisor_external {
+            bits |= 1 << 9;
+        }
+        bits
+    }
+}
+
+impl Default for Delegation {
+    #[inline]
+    fn default() -> Self {
+        Self::STANDARD
+    }
+}
+
+/// Writes `medeleg` and `mideleg` to delegate the traps of `delegation` to S-mode.
+///
+/// Both registers are overwritten, so traps not included in `delegation` are handled in M-mode.
+/// Delegation bits not implemented by the platform are ignored by the hardware.
+///
+/// # Safety
+///
+/// S-mode must be able to handle all the delegated traps (i.e., `stvec` must be set up before entering S-mode).
+#[inline]
+pub unsafe fn delegate_standard_to_supervisor(delegation: Delegation) {
+    medeleg::write(delegation.medeleg());
+    mideleg::write(delegation.mideleg());
+}
+
+/// Structured handler of M-mode exceptions.
+///
+/// Each method handles one kind of exception. By default, all of them call
+/// [`ExceptionHandler::unhandled`], which panics with the [`Report`] of the trap.
+/// Implementors only override the methods of the exceptions they handle.
+///
+/// When a method returns, execution resumes at `mepc`. Handlers of exceptions that
+/// must not be retried (e.g., environment calls) must advance `mepc` themselves.
+///
+/// # Example
+///
+/// ``` no_run
+/// use riscv::register::mepc;
+/// use riscv::trap::{ExceptionHandler, Report};
+///
+/// struct Handler;
+///
+/// impl ExceptionHandler for Handler {
+///     fn machine_env_call(report: &Report) {
+///         // skip the ecall instruction
+///         mepc::write(report.mepc + 4);
+///     }
+/// }
+///
+/// riscv::exception_handler!(Handler);
+/// ```
+pub trait ExceptionHandler {
+    /// Instruction address misaligned.
+    #[inline]
+    fn instruction_misaligned(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Instruction access fault.
+    #[inline]
+    fn instruction_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Illegal instruction.
+    #[inline]
+    fn illegal_instruction(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Breakpoint.
+    #[inline]
+    fn breakpoint(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Load address misaligned.
+    #[inline]
+    fn load_misaligned(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Load access fault.
+    #[inline]
+    fn load_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Store/AMO address misaligned.
+    #[inline]
+    fn store_misaligned(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Store/AMO access fault.
+    #[inline]
+    fn store_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Environment call from U-mode.
+    #[inline]
+    fn user_env_call(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Environment call from S-mode.
+    #[inline]
+    fn supervisor_env_call(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Environment call from M-mode.
+    #[inline]
+    fn machine_env_call(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Instruction page fault.
+    #[inline]
+    fn instruction_page_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Load page fault.
+    #[inline]
+    fn load_page_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Store/AMO page fault.
+    #[inline]
+    fn store_page_fault(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Double trap (Smdbltrp/Ssdbltrp).
+    ///
+    /// The trap that could not be taken is described by `mtval2`, if implemented.
+    #[inline]
+    fn double_trap(report: &Report) {
+        Self::unhandled(report)
+    }
+
+    /// Unrecognized or unhandled exception.
+    #[inline]
+    fn unhandled(report: &Report) -> ! {
+        panic!("unhandled exception: {}", report)
+    }
+}
+
+/// Reads the trap CSRs and calls the method of `H` that corresponds to the exception cause.
+///
+/// It is meant to be called from the exception handler (see [`crate::exception_handler`]).
+/// If the last trap was an interrupt, it calls [`ExceptionHandler::unhandled`].
+#[inline]
+pub fn handle_exception<H: ExceptionHandler>() {
+    let report = Report::read();
+    match report.mcause.cause() {
+        mcause::Trap::Exception(mcause::Exception::InstructionMisaligned) => {
+            H::instruction_misaligned(&report)
+        }
+        mcause::Trap::Exception(mcause::Exception::InstructionFault) => {
+            H::instruction_fault(&report)
+        }
+        mcause::Trap::Exception(mcause::Exception::IllegalInstruction) => {
+            H::illegal_instruction(&report)
+        }
+        mcause::Trap::Exception(mcause::Exception::Breakpoint) => H::breakpoint(&report),
+        mcause::Trap::Exception(mcause::Exception::LoadMisaligned) => H::load_misaligned(&report),
+        mcause::Trap::Exception(mcause::Exception::LoadFault) => H::load_fault(&report),
+        mcause::Trap::Exception(mcause::Exception::StoreMisaligned) => H::store_misaligned(&report),
+        mcause::Trap::Exception(mcause::Exception::StoreFault) => H::store_fault(&report),
+        mcause::Trap::Exception(mcause::Exception::UserEnvCall) => H::user_env_call(&report),
+        mcause::Trap::Exception(mcause::Exception::SupervisorEnvCall) => {
+            H::supervisor_env_call(&report)
+        }
+        mcause::Trap::Exception(mcause::Exception::MachineEnvCall) => H::machine_env_call(&report),
+        mcause::Trap::Exception(mcause::Exception::InstructionPageFault) => {
+            H::instruction_page_fault(&report)
+        }
+        mcause::Trap::Exception(mcause::Exception::LoadPageFault) => H::load_page_fault(&report),
+        mcause::Trap::Exception(mcause::Exception::StorePageFault) => H::store_page_fault(&report),
+        mcause::Trap::Exception(mcause::Exception::DoubleTrap) => H::double_trap(&report),
+        _ => H::unhandled(&report),
+    }
+}
+
+/// Description of the last trap taken in M-mode
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+    /// Cause of the trap
+    pub mcause: mcause::Mcause,
+    /// Address of the instruction that was interrupted or raised the exception
+    pub mepc: usize,
+    /// Exception-specific information (e.g., the faulting address)
+    pub mtval: usize,
+    /// Machine status when the trap was taken
+    pub mstatus: mstatus::Mstatus,
+}
+
+impl Report {
+    /// Reads the M-mode trap CSRs
+    #[inline]
+    pub fn read() -> Self {
+        Self {
+            mcause: mcause::read(),
+            mepc: mepc::read(),
+            mtval: mtval::read(),
+            mstatus: mstatus::read(),
+        }
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at {:#x} (mtval: {:#x}, mstatus: {:#x}, MPP: {:?})",
+            self.mcause,
+            self.mepc,
+            self.mtval,
+            self.mstatus.bits(),
+            self.mstatus.mpp()
+        )
+    }
+}
+
+/// Description of the last trap taken in S-mode
+#[derive(Clone, Copy, Debug)]
+pub struct SupervisorReport {
+    /// Cause of the trap
+    pub scause: scause::Scause,
+    /// Address of the instruction that was interrupted or raised the exception
+    pub sepc: usize,
+    /// Exception-specific information (e.g., the faulting address)
+    pub stval: usize,
+    /// Supervisor status when the trap was taken
+    pub sstatus: sstatus::Sstatus,
+}
+
+impl SupervisorReport {
+    /// Reads the S-mode trap CSRs
+    #[inline]
+    pub fn read() -> Self {
+        Self {
+            scause: scause::read(),
+            sepc: sepc::read(),
+            stval: stval::read(),
+            sstatus: sstatus::read(),
+        }
+    }
+}
+
+impl fmt::Display for SupervisorReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} at {:#x} (stval: {:#x}, sstatus: {:#x}, SPP: {:?})",
+            self.scause,
+            self.sepc,
+            self.stval,
+            self.sstatus.bits(),
+            self.sstatus.spp()
+        )
+    }
+}
+
+/// Per-hart flags that detect traps taken inside the trap handler (i.e., double faults).
+///
+/// The trap handler calls [`Reentrancy::enter`] (or [`Reentrancy::enter_or`]) at the beginning
+/// and keeps the returned [`TrapGuard`] until it returns. If another trap is taken in the meantime
+/// (e.g., because the trap stack is corrupted), entering again fails for the current hart.
+///
+/// # Note
+///
+/// Each hart only accesses its own flag, indexed by `mhartid`. `HARTS` must be greater than
+/// the highest hart ID of the platform.
+///
+/// # Example
+///
+/// ``` no_run
+/// use riscv::trap::{Reentrancy, Report};
+///
+/// static REENTRANCY: Reentrancy<1> = Reentrancy::new();
+///
+/// fn double_fault(report: Report) -> ! {
+///     panic!("double fault: {}", report);
+/// }
+///
+/// fn trap_handler() {
+///     let _guard = REENTRANCY.enter_or(double_fault);
+///     // handle the trap here
+/// }
+/// ```
+pub struct Reentrancy<const HARTS: usize> {
+    active: UnsafeCell<[bool; HARTS]>,
+}
+
+// SAFETY: each hart only accesses its own flag
+unsafe impl<const HARTS: usize> Sync for Reentrancy<HARTS> {}
+
+impl<const HARTS: usize> Reentrancy<HARTS> {
+    /// Creates a new set of flags, with no hart inside the trap handler.
+    #[inline]
+    pub const fn new() -> Self {
+        Self {
+            active: UnsafeCell::new([false; HARTS]),
+        }
+    }
+
+    /// Returns a pointer to the flag of the current hart.
+    #[inline]
+    fn flag(&self) -> *mut bool {
+        let hart = mhartid::read();
+        assert!(hart < HARTS, "hart ID out of range");
+        // SAFETY: hart is within the array
+        unsafe { (self.active.get() as *mut bool).add(hart) }
+    }
+
+    /// Checks if the current hart is inside the trap handler.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        // SAFETY: each hart only accesses its own flag
+        unsafe { self.flag().read_volatile() }
+    }
+
+    /// Marks the current hart as inside the trap handler.
+    ///
+    /// If the current hart already is inside the trap handler, it returns an error with
+    /// the [`Report`] of the nested trap.
+    #[inline]
+    pub fn enter(&self) -> Result<TrapGuard<'_, HARTS>, Report> {
+        let flag = self.flag();
+        // SAFETY: each hart only accesses its own flag
+        unsafe {
+            if flag.read_volatile() {
+                return Err(Report::read());
+            }
+            flag.write_volatile(true);
+        }
+        Ok(TrapGuard { flag, _owner: self })
+    }
+
+    /// Marks the current hart as inside the trap handler.
+    ///
+    /// If the current hart already is inside the trap handler, it calls `double_fault`
+    /// with the [`Report`] of the nested trap.
+    #[inline]
+    pub fn enter_or(&self, double_fault: fn(Report) -> !) -> TrapGuard<'_, HARTS> {
+        match self.enter() {
+            Ok(guard) => guard,
+            Err(report) => double_fault(report),
+        }
+    }
+}
+
+impl<const HARTS: usize> Default for Reentrancy<HARTS> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Guard that marks the current hart as inside the trap handler.
+///
+/// The mark is cleared when the guard is dropped.
+pub struct TrapGuard<'a, const HARTS: usize> {
+    flag: *mut bool,
+    _owner: &'a Reentrancy<HARTS>,
+}
+
+impl<'a, const HARTS: usize> Drop for TrapGuard<'a, HARTS> {
+    #[inline]
+    fn drop(&mut self) {
+        // SAFETY: each hart only accesses its own flag
+        unsafe { self.flag.write_volatile(false) };
+    }
+}
@@ -0,0 +1,283 @@
+//! Decoder of faulting memory access instructions
+//!
+//! Trap handlers that emulate misaligned accesses (or report faults in detail) need to know
+//! which kind of access raised the exception, its width, and the registers it uses.
+//! [`decode`] extracts this information from the raw bits of an instruction, without
+//! decoding anything else (i.e., it is not a disassembler).
+//!
+//! The instruction bits can be obtained from:
+//!
+//! - `mtinst`/`htinst`, if implemented (see [`decode_mtinst`]).
+//! - `mtval`, for illegal instruction exceptions on platforms that report the instruction bits.
+//! - The instruction memory at `mepc` (see [`fetch`]).
+//!
+//! # Example
+//!
+//! ```
+//! use riscv::register::xlen::Xlen;
+//! use riscv::trap::decode::{decode, Class, Width};
+//!
+//! // lw a0, 4(a1)
+//! let insn = decode(0x0045_a503, Xlen::Xlen64);
+//! assert_eq!(insn.class, Class::Load);
+//! assert_eq!(insn.width, Some(Width::Word));
+//! assert_eq!((insn.rd, insn.rs1, insn.rs2), (Some(10), Some(11), None));
+//! assert_eq!(insn.len, 4);
+//!
+//! // c.sw a0, 0(a1)
+//! let insn = decode(0xc188, Xlen::Xlen64);
+//! assert_eq!(insn.class, Class::Store);
+//! assert_eq!((insn.rd, insn.rs1, insn.rs2), (None, Some(11), Some(10)));
+//! assert_eq!(insn.len, 2);
+//! ```
+
+use crate::register::xlen::Xlen;
+
+/// Class of a decoded instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Class {
+    /// Integer load
+    Load,
+    /// Integer store
+    Store,
+    /// Floating-point load (`rd` is a floating-point register)
+    LoadFp,
+    /// Floating-point store (`rs2` is a floating-point register)
+    StoreFp,
+    /// Atomic memory operation, including load-reserved and store-conditional
+    Amo,
+    /// Any other instruction
+    Other,
+}
+
+/// Width of a memory access
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Width {
+    /// 8 bits
+    Byte = 1,
+    /// 16 bits
+    Half = 2,
+    /// 32 bits
+    Word = 4,
+    /// 64 bits
+    Double = 8,
+}
+
+impl Width {
+    /// Returns the number of bytes of the access
+    #[inline]
+    pub const fn bytes(self) -> usize {
+        self as usize
+    }
+}
+
+/// Decoded instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instruction {
+    /// Raw bits of the instruction. Compressed instructions only use the lower 16 bits
+    pub bits: u32,
+    /// Length (in bytes) of the instruction
+    pub len: usize,
+    /// Class of the instruction
+    pub class: Class,
+    /// Destination register
+    pub rd: Option<u8>,
+    /// First source register (the base address of memory accesses)
+    pub rs1: Option<u8>,
+    /// Second source register (the stored value of stores)
+    pub rs2: Option<u8>,
+    /// Width of the memory access
+    pub width: Option<Width>,
+    /// Whether a load zero-extends the loaded value (e.g., `lbu`) instead of sign-extending it
+    pub unsigned: bool,
+}
+
+impl Instruction {
+    /// Creates a decoded instruction with no registers and no memory access.
+    #[inline]
+    const fn new(bits: u32, len: usize, class: Class) -> Self {
+        Self {
+            bits,
+            len,
+            class,
+            rd: None,
+            rs1: None,
+            rs2: None,
+            width: None,
+            unsigned: false,
+        }
+    }
+
+    /// Checks if the instruction accesses memory
+    #[inline]
+    pub const fn is_memory_access(&self) -> bool {
+        self.width.is_some()
+    }
+}
+
+/// Decodes the memory access of an instruction.
+///
+/// `bits` may contain a 32-bit instruction or a compressed instruction in its lower 16 bits.
+/// Some compressed encodings depend on the XLEN of the hart (e.g., `c.ld` in RV64 is `c.flw` in RV32).
+/// Instructions that do not access memory are decoded as [`Class::Other`] without registers.
+pub const fn decode(bits: u32, xlen: Xlen) -> Instruction {
+    if bits & 0b11 != 0b11 {
+        return decode_compressed(bits & 0xffff, xlen);
+    }
+    let rd = Some(((bits >> 7) & 0x1f) as u8);
+    let rs1 = Some(((bits >> 15) & 0x1f) as u8);
+    let rs2 = Some(((bits >> 20) & 0x1f) as u8);
+    let funct3 = (bits >> 12) & 0b111;
+    let mut insn = Instruction::new(bits, 4, Class::Other);
+    match bits & 0x7f {
+        0x03 => {
+            insn.width = match funct3 {
+                0b000 | 0b100 => Some(Width::Byte),
+                0b001 | 0b101 => Some(Width::Half),
+                0b010 | 0b110 => Some(Width::Word),
+                0b011 => Some(Width::Double),
+                _ => return insn,
+            };
+            insn.class = Class::Load;
+            insn.rd = rd;
+            insn.rs1 = rs1;
+            insn.unsigned = funct3 & 0b100 != 0;
+        }
+        0x23 => {
+            insn.width = match funct3 {
+                0b000 => Some(Width::Byte),
+                0b001 => Some(Width::Half),
+                0b010 => Some(Width::Word),
+                0b011 => Some(Width::Double),
+                _ => return insn,
+            };
+            insn.class = Class::Store;
+            insn.rs1 = rs1;
+            insn.rs2 = rs2;
+        }
+        0x07 | 0x27 => {
+            insn.width = match funct3 {
+                0b001 => Some(Width::Half),
+                0b010 => Some(Width::Word),
+                0b011 => Some(Width::Double),
+                _ => return insn,
+            };
+            insn.rs1 = rs1;
+            if bits & 0x7f == 0x07 {
+                insn.class = Class::LoadFp;
+                insn.rd = rd;
+            } else {
+                insn.class = Class::StoreFp;
+                insn.rs2 = rs2;
+            }
+        }
+        0x2f => {
+            insn.width = match funct3 {
+                0b010 => Some(Width::Word),
+                0b011 => Some(Width::Double),
+                _ => return insn,
+            };
+            insn.class = Class::Amo;
+            insn.rd = rd;
+            insn.rs1 = rs1;
+            insn.rs2 = rs2;
+        }
+        _ => {}
+    }
+    insn
+}
+
+/// Decodes the memory access of a compressed instruction.
+const fn decode_compressed(bits: u32, xlen: Xlen) -> Instruction {
+    let rv64 = matches!(xlen, Xlen::Xlen64);
+    let mut insn = Instruction::new(bits, 2, Class::Other);
+    let (class, width) = match (bits & 0b11, (bits >> 13) & 0b111) {
+        (0b00 | 0b10, 0b001) => (Class::LoadFp, Width::Double),
+        (0b00 | 0b10, 0b010) => (Class::Load, Width::Word),
+        (0b00 | 0b10, 0b011) if rv64 => (Class::Load, Width::Double),
+        (0b00 | 0b10, 0b011) => (Class::LoadFp, Width::Word),
+        (0b00 | 0b10, 0b101) => (Class::StoreFp, Width::Double),
+        (0b00 | 0b10, 0b110) => (Class::Store, Width::Word),
+        (0b00 | 0b10, 0b111) if rv64 => (Class::Store, Width::Double),
+        (0b00 | 0b10, 0b111) => (Class::StoreFp, Width::Word),
+        _ => return insn,
+    };
+    insn.class = class;
+    insn.width = Some(width);
+    let load = matches!(class, Class::Load | Class::LoadFp);
+    if bits & 0b11 == 0b00 {
+        // compressed registers x8-x15 (or f8-f15)
+        let reg = Some((((bits >> 2) & 0b111) + 8) as u8);
+        insn.rs1 = Some((((bits >> 7) & 0b111) + 8) as u8);
+        match load {
+            true => insn.rd = reg,
+            false => insn.rs2 = reg,
+        }
+    } else {
+        // stack pointer-based
+        insn.rs1 = Some(2);
+        match load {
+            true => insn.rd = Some(((bits >> 7) & 0x1f) as u8),
+            false => insn.rs2 = Some(((bits >> 2) & 0x1f) as u8),
+        }
+    }
+    insn
+}
+
+/// Decodes the transformed instruction reported in `mtinst` (or `htinst`).
+///
+/// It returns [`None`] if `mtinst` is zero (no instruction is reported)
+/// or contains a pseudoinstruction (bit 0 is cleared).
+/// Transformed compressed instructions are reported as 32-bit instructions with bit 1 cleared,
+/// so the returned instruction keeps a length of 2 bytes.
+///
+/// In transformed memory accesses, the `rs1` field holds the offset of the faulting address
+/// instead of the base register, so [`Instruction::rs1`] is [`None`] for them.
+///
+/// # Example
+///
+/// ```
+/// use riscv::register::xlen::Xlen;
+/// use riscv::trap::decode::{decode_mtinst, Class};
+///
+/// // transformed lw a0, 4(a1) with an address offset of 2
+/// let insn = decode_mtinst(0x0001_2503, Xlen::Xlen64).unwrap();
+/// assert_eq!(insn.class, Class::Load);
+/// assert_eq!((insn.rd, insn.rs1), (Some(10), None));
+/// ```
+#[inline]
+pub const fn decode_mtinst(mtinst: usize, xlen: Xlen) -> Option<Instruction> {
+    let bits = mtinst as u32;
+    if bits & 1 == 0 {
+        return None;
+    }
+    let mut insn = decode(bits | 0b10, xlen);
+    if bits & 0b10 == 0 {
+        insn.bits = bits;
+        insn.len = 2;
+    }
+    if !matches!(insn.class, Class::Other) {
+        insn.rs1 = None;
+    }
+    Some(insn)
+}
+
+/// Reads the instruction at `epc` (e.g., `mepc`).
+///
+/// It reads 16-bit parcels, as instructions are only 2-byte aligned with the C extension.
+/// The upper parcel is only read if the lower one belongs to a 32-bit instruction.
+///
+/// # Safety
+///
+/// `epc` must point to readable memory with the current address translation.
+/// Note that M-mode handlers of traps taken from S-mode or U-mode with virtual memory enabled
+/// must translate `epc` first (e.g., with `mstatus.MPRV`).
+#[inline]
+pub unsafe fn fetch(epc: usize) -> u32 {
+    let ptr = epc as *const u16;
+    let lo = ptr.read_volatile() as u32;
+    match lo & 0b11 {
+        0b11 => lo | ((ptr.add(1).read_volatile() as u32) << 16),
+        _ => lo,
+    }
+}